@@ -0,0 +1,65 @@
+use aoc_common::{Answer, Solution};
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    type Input = String;
+
+    fn parse(input: &str) -> Self::Input {
+        input.to_string()
+    }
+
+    fn part1(line: &Self::Input) -> Answer {
+        count_floors(line).into()
+    }
+
+    fn part2(line: &Self::Input) -> Answer {
+        find_basement(line).into()
+    }
+}
+
+fn count_floors(line: &str) -> i32 {
+    line.chars().fold(0, |acc, c| match c {
+        '(' => acc + 1,
+        ')' => acc - 1,
+        _ => acc,
+    })
+}
+
+fn find_basement(line: &str) -> i32 {
+    let mut floor = 0;
+
+    for (i, c) in line.chars().enumerate() {
+        floor = match c {
+            '(' => floor + 1,
+            ')' => floor - 1,
+            _ => floor,
+        };
+        if floor == -1 {
+            return i as i32 + 1;
+        }
+    }
+
+    0
+}
+
+#[test]
+fn test_count_floors_0() {
+    let count = count_floors("(())");
+
+    assert_eq!(count, 0);
+}
+
+#[test]
+fn test_basement_floor() {
+    let basement_floor = find_basement(")");
+
+    assert_eq!(basement_floor, 1);
+}
+
+#[test]
+fn test_no_basement() {
+    let basement_floor = find_basement("((");
+
+    assert_eq!(basement_floor, 0);
+}
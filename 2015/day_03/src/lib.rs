@@ -0,0 +1,78 @@
+use aoc_common::{Answer, Point2, Solution, SparseGrid};
+
+fn count_visits(path: &str) -> u32 {
+    let mut houses = SparseGrid::new();
+    deliver(path.chars(), &mut houses);
+    houses.len() as u32
+}
+
+/// Leaves a present at every house along the path, starting with the first
+fn deliver(path: impl Iterator<Item = char>, houses: &mut SparseGrid<u32>) {
+    let mut leave_present = |position: Point2| {
+        let presents = houses.get(position).map_or(1, |n| n + 1);
+        houses.insert(position, presents);
+    };
+
+    let mut position = Point2::ORIGIN;
+    leave_present(position);
+
+    for c in path {
+        match c {
+            '^' => position.y += 1,
+            'v' => position.y -= 1,
+            '>' => position.x += 1,
+            '<' => position.x -= 1,
+            _ => (),
+        }
+        leave_present(position);
+    }
+}
+
+fn santa_and_robo_visits(path: &str) -> u32 {
+    let mut houses = SparseGrid::new();
+    deliver(path.chars().step_by(2), &mut houses);
+    deliver(path.chars().skip(1).step_by(2), &mut houses);
+    houses.len() as u32
+}
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    type Input = String;
+
+    fn parse(input: &str) -> Self::Input {
+        input.lines().next().unwrap().to_string()
+    }
+
+    fn part1(path: &Self::Input) -> Answer {
+        count_visits(path).into()
+    }
+
+    fn part2(path: &Self::Input) -> Answer {
+        santa_and_robo_visits(path).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case(">", 2)]
+    #[test_case("^>v<", 4)]
+    #[test_case("^v^v^v^v^v", 2)]
+    fn test_santa_travel(path: &str, expected: u32) {
+        let houses_visited = count_visits(path);
+
+        assert_eq!(houses_visited, expected);
+    }
+
+    #[test_case("^v", 3; "Simple 3 steps")]
+    #[test_case("^>v<", 3)]
+    #[test_case("^v^v^v^v^v", 11)]
+    fn test_robo_santa_travel(path: &str, expected: u32) {
+        let houses_visited = santa_and_robo_visits(path);
+
+        assert_eq!(houses_visited, expected);
+    }
+}
@@ -0,0 +1,55 @@
+use aoc_common::{Answer, Solution};
+use md5::{self, Digest};
+
+fn calculate_hash(code: &str, value: u32) -> Digest {
+    let mut hasher = md5::Context::new();
+    hasher.consume(code);
+    hasher.consume(value.to_string());
+    hasher.compute()
+}
+
+fn find_hash(code: &str, prefix: &str) -> u32 {
+    let mut value = 0;
+    loop {
+        let hash = calculate_hash(code, value);
+        let hash_string = format!("{:x}", hash);
+        if hash_string.starts_with(prefix) {
+            break;
+        }
+        value += 1;
+    }
+
+    value
+}
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    type Input = String;
+
+    fn parse(input: &str) -> Self::Input {
+        input.lines().next().unwrap().to_string()
+    }
+
+    fn part1(code: &Self::Input) -> Answer {
+        find_hash(code, "00000").into()
+    }
+
+    fn part2(code: &Self::Input) -> Answer {
+        find_hash(code, "000000").into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case("abcdef", 609043)]
+    #[test_case("pqrstuv", 1048970)]
+    fn test_five_zero_hash(path: &str, expected: u32) {
+        let hash_value = find_hash(path, "00000");
+
+        assert_eq!(hash_value, expected);
+    }
+}
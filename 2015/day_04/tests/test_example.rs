@@ -13,7 +13,9 @@ fn test_example() -> Result<(), Box<dyn std::error::Error>> {
     let mut contents = String::new();
     file.read_to_string(&mut contents)?;
 
-    cmd.write_stdin(contents)
+    // The example only has an answer for part 1
+    cmd.env("AOC_PART", "1")
+        .write_stdin(contents)
         .assert()
         .stdout("Prefix 00000: 609043\n");
 
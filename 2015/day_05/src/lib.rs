@@ -0,0 +1,74 @@
+use aoc_common::{Answer, Solution};
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    type Input = Vec<String>;
+
+    fn parse(input: &str) -> Self::Input {
+        input.lines().map(String::from).collect()
+    }
+
+    fn part1(strings: &Self::Input) -> Answer {
+        strings.iter().filter(|&line| is_nice(line)).count().into()
+    }
+
+    fn part2(strings: &Self::Input) -> Answer {
+        strings.iter().filter(|&line| is_nice2(line)).count().into()
+    }
+}
+
+fn is_nice(s: &str) -> bool {
+    let mut test = s.chars().filter(|c| "aeiou".contains(*c)).count() >= 3;
+    test &= s.chars().zip(s.chars().skip(1)).any(|(a, b)| a == b);
+    test &= !s.contains("ab") && !s.contains("cd") && !s.contains("pq") && !s.contains("xy");
+
+    test
+}
+
+fn is_nice2(s: &str) -> bool {
+    let mut test = s.chars().zip(s.chars().skip(2)).any(|(a, b)| a == b);
+    test &= s
+        .chars()
+        .zip(s.chars().skip(1))
+        .enumerate()
+        .any(|(i, (a, b))| {
+            // println!("1 {} {} {}", i, a, b);
+            s.chars()
+                .skip(i + 2)
+                .zip(s.chars().skip(i + 3))
+                .any(|(c, d)| {
+                    // println!("2 {} {} {}", i, c, d);
+                    a == c && b == d
+                })
+        });
+
+    test
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case("ugknbfddgicrmopn", true)]
+    #[test_case("aaa", true)]
+    #[test_case("jchzalrnumimnmhp", false)]
+    #[test_case("haegwjzuvuyypxyu", false)]
+    #[test_case("dvszwmarrgswjxmb", false)]
+    fn test_naughty_nice(s: &str, expected: bool) {
+        let is_nice = is_nice(s);
+
+        assert_eq!(is_nice, expected);
+    }
+
+    #[test_case("qjhvhtzxzqqjkmpb", true)]
+    #[test_case("xxyxx", true)]
+    #[test_case("uurcxstgmygtbstg", false)]
+    #[test_case("ieodomkazucvgmuy", false)]
+    fn test_naughty_nice2(s: &str, expected: bool) {
+        let is_nice = is_nice2(s);
+
+        assert_eq!(is_nice, expected);
+    }
+}
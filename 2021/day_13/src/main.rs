@@ -1,8 +1,8 @@
-use std::collections::HashMap;
 use std::error::Error;
 use std::fmt;
 use std::io::{self, prelude::*};
 
+use aoc_common::{sparse::Bounds, Point2, SparseGrid};
 use itertools::Itertools;
 
 pub struct Instruction {
@@ -24,105 +24,84 @@ impl fmt::Display for Instruction {
     }
 }
 
-#[derive(Debug, Hash, PartialEq, Eq, Clone, Copy)]
-pub struct Point {
-    x: u32,
-    y: u32,
-}
-
-impl Point {
-    pub fn new(x: u32, y: u32) -> Point {
-        Point { x, y }
-    }
-}
-
 struct TransparentPaper {
-    grid: HashMap<Point, bool>,
-    width: u32,
-    height: u32,
+    dots: SparseGrid<()>,
+    width: isize,
+    height: isize,
 }
 
 impl TransparentPaper {
     pub fn new() -> TransparentPaper {
         TransparentPaper {
-            grid: HashMap::new(),
+            dots: SparseGrid::new(),
             width: 0,
             height: 0,
         }
     }
 
-    pub fn add_point(&mut self, point: Point) {
-        self.grid.insert(point, true);
-    }
-
-    pub fn width(&self) -> u32 {
-        self.width
-    }
-
-    pub fn height(&self) -> u32 {
-        self.height
+    pub fn add_point(&mut self, point: Point2) {
+        self.dots.insert(point, ());
     }
 
     pub fn count_points(&self) -> u32 {
-        self.grid.len() as u32
+        self.dots.len() as u32
     }
 
     fn apply_fold(&mut self, instruction: &Instruction) {
-        // let height = self.height();
-        // let width = self.width();
+        let val = instruction.val as isize;
 
-        let mut new_grid = HashMap::new();
+        let mut new_dots = SparseGrid::new();
 
-        for &point in self.grid.keys() {
+        for point in self.dots.points() {
             if instruction.dir == 'y' {
-                if point.y > self.height - instruction.val - 1 {
+                if point.y > self.height - val - 1 {
                     let new_y = self.height - point.y - 1;
 
-                    new_grid.insert(Point::new(point.x, new_y), true);
+                    new_dots.insert(Point2::new(point.x, new_y), ());
                 } else {
-                    new_grid.insert(point, true);
+                    new_dots.insert(point, ());
                 }
             } else if instruction.dir == 'x' {
-                if point.x > self.width - instruction.val - 1 {
+                if point.x > self.width - val - 1 {
                     let new_x = self.width - point.x - 1;
 
-                    new_grid.insert(Point::new(new_x, point.y), true);
+                    new_dots.insert(Point2::new(new_x, point.y), ());
                 } else {
-                    new_grid.insert(point, true);
+                    new_dots.insert(point, ());
                 }
             }
         }
 
         if instruction.dir == 'y' {
-            self.height = self.height - instruction.val - 1;
+            self.height = self.height - val - 1;
         } else if instruction.dir == 'x' {
-            self.width = self.width - instruction.val - 1;
+            self.width = self.width - val - 1;
         }
 
-        self.grid = new_grid;
+        self.dots = new_dots;
     }
 
     pub fn measure(&mut self) {
-        self.width = self.grid.keys().map(|p| p.x).max().unwrap() as u32 + 1;
-        self.height = self.grid.keys().map(|p| p.y).max().unwrap() as u32 + 1;
+        let bounds = self.dots.bounds().unwrap();
+        self.width = bounds.max.x + 1;
+        self.height = bounds.max.y + 1;
     }
 }
 
 impl fmt::Display for TransparentPaper {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        for y in 0..self.height() {
-            for x in 0..self.width() {
-                let point = Point::new(x as u32, y as u32);
-                if self.grid.contains_key(&point) {
-                    write!(f, "#")?;
-                } else {
-                    write!(f, ".")?;
-                }
-            }
-            writeln!(f)?;
-        }
-
-        Ok(())
+        // The folded paper keeps its size even where its edges have no dots
+        let paper = Bounds {
+            min: Point2::ORIGIN,
+            max: Point2::new(self.width - 1, self.height - 1),
+        };
+
+        write!(
+            f,
+            "{}",
+            self.dots
+                .render_within(paper, |dot| if dot.is_some() { '#' } else { '.' })
+        )
     }
 }
 
@@ -139,7 +118,7 @@ fn main() -> Result<(), Box<dyn Error>> {
                 .split(',')
                 .map(|x| x.parse::<u32>().unwrap())
                 .collect_vec();
-            paper.add_point(Point::new(points[0], points[1]));
+            paper.add_point(Point2::new(points[0] as isize, points[1] as isize));
             paper.measure();
         }
 
@@ -1,13 +1,22 @@
 use std::collections::HashMap;
 use std::error::Error;
-use std::hash::Hash;
 use std::io::{self, prelude::*};
 
-use counter::Counter;
+use aoc_common::memo::Memo;
+
+type Counts = HashMap<char, u64>;
 
 struct PolymerBuilder {
     polymer: String,
-    rules: HashMap<String, String>,
+    rules: HashMap<(char, char), char>,
+    /// What ends up between a pair after some number of steps
+    inserted: Memo<(char, char, usize), Counts>,
+}
+
+fn add_counts(total: &mut Counts, more: &Counts) {
+    for (&c, &n) in more {
+        *total.entry(c).or_insert(0) += n;
+    }
 }
 
 impl PolymerBuilder {
@@ -15,44 +24,48 @@ impl PolymerBuilder {
         Self {
             polymer: initial_polymer.to_string(),
             rules: HashMap::new(),
+            inserted: Memo::new(),
         }
     }
 
     fn add_rule(&mut self, from: &str, to: &str) {
-        self.rules.insert(from.to_string(), to.to_string());
-    }
-
-    fn get_polymer(&self) -> &str {
-        &self.polymer
+        let mut from = from.chars();
+        if let (Some(a), Some(b), Some(to)) = (from.next(), from.next(), to.chars().next()) {
+            self.rules.insert((a, b), to);
+        }
     }
 
-    fn step(&mut self) {
-        let mut new_segments = Vec::new();
+    /// Counts the elements after the steps without building the polymer, which
+    /// doubles in length every step
+    fn counts_after(&mut self, steps: usize) -> Counts {
+        let rules = &self.rules;
+        let mut counts = Counts::new();
 
-        self.polymer
-            .chars()
-            .zip(self.polymer.chars().skip(1))
-            .for_each(|(a, b)| {
-                let segment = format!("{}{}", a, b);
-                // println!("{}", segment);
-                new_segments.push(self.rules[&segment].to_string());
-            });
-
-        let mut new_polymer = String::new();
-        new_segments.reverse();
+        for c in self.polymer.chars() {
+            *counts.entry(c).or_insert(0) += 1;
+        }
 
-        self.polymer.chars().for_each(|c| {
-            new_polymer.push(c);
-            new_polymer.extend(new_segments.pop());
-        });
+        for (a, b) in self.polymer.chars().zip(self.polymer.chars().skip(1)) {
+            let between = self
+                .inserted
+                .recurse((a, b, steps), &|inserted, (a, b, steps)| {
+                    let mut counts = Counts::new();
+                    if let (Some(&c), true) = (rules.get(&(a, b)), steps > 0) {
+                        counts.insert(c, 1);
+                        add_counts(&mut counts, &inserted((a, c, steps - 1)));
+                        add_counts(&mut counts, &inserted((c, b, steps - 1)));
+                    }
+                    counts
+                });
+            add_counts(&mut counts, &between);
+        }
 
-        self.polymer = new_polymer;
+        counts
     }
 
-    fn calculate_most_least_common(&self) -> u64 {
-        let counts = self.polymer.chars().collect::<Counter<_>>().most_common_ordered();
-        // println!("{:?}", counts);
-        counts.first().unwrap().1 as u64 - counts.last().unwrap().1 as u64
+    fn calculate_most_least_common(&mut self, steps: usize) -> u64 {
+        let counts = self.counts_after(steps);
+        counts.values().max().unwrap() - counts.values().min().unwrap()
     }
 }
 
@@ -66,28 +79,19 @@ fn main() -> Result<(), Box<dyn Error>> {
     for line in lines.flatten() {
         if line.contains(" -> ") {
             let (from, to) = line.split_once(" -> ").unwrap();
-            polymer_builder.add_rule(
-                from, 
-                to
-            );
+            polymer_builder.add_rule(from, to);
         }
     }
 
-    // println!("{}", polymer_builder.get_polymer());
-
-    for _ in 1..=10 {
-        polymer_builder.step();
-    }
-
-    println!("After 10 steps, most common element minus least common element: {}", polymer_builder.calculate_most_least_common());
-
-    for _ in 1..=30 {
-        polymer_builder.step();
-    }
-
-    println!("After 40 steps, most common element minus least common element: {}", polymer_builder.calculate_most_least_common());
-
+    println!(
+        "After 10 steps, most common element minus least common element: {}",
+        polymer_builder.calculate_most_least_common(10)
+    );
 
+    println!(
+        "After 40 steps, most common element minus least common element: {}",
+        polymer_builder.calculate_most_least_common(40)
+    );
 
     Ok(())
 }
@@ -1,5 +1,6 @@
 use std::io::{self, prelude::*};
 
+use aoc_common::math;
 use regex::Regex;
 
 #[derive(Debug)]
@@ -19,10 +20,12 @@ impl MonkeyBusiness {
     }
 
     fn chuck_items(&mut self, with_stress_relief: bool) {
-        let monkey_mod: usize = self
-            .monkeys
-            .iter()
-            .fold(1, |acc, m| acc * (m.test_divisible_condition as usize));
+        // Every test still gives the same answer with worry kept below this
+        let monkey_mod: usize = math::lcm_all(
+            self.monkeys
+                .iter()
+                .map(|m| m.test_divisible_condition as usize),
+        );
 
         for i in 0..self.monkeys.len() {
             let monkey = self.monkeys[i].clone();
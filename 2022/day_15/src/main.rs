@@ -1,10 +1,11 @@
 use std::{
-    collections::{HashMap, HashSet},
+    collections::HashSet,
     env::args,
     fmt::Debug,
     io::{self, BufRead},
 };
 
+use aoc_common::interval::IntervalSet;
 use regex::Regex;
 
 #[derive(Debug)]
@@ -80,12 +81,8 @@ fn main() {
         sensors.push(sensor);
     }
 
-    let no_beacon_rows = find_no_beacon_spaces(&sensors, min, max);
-    let beacon_ranges = no_beacon_rows.get(&row).unwrap();
-    let min_beacon_range = beacon_ranges.iter().min_by_key(|r| r.0).unwrap().0;
-    let max_beacon_range = beacon_ranges.iter().max_by_key(|r| r.1).unwrap().1;
-
-    let no_beacons = max_beacon_range - min_beacon_range;
+    let no_beacons = find_no_beacon_spaces(&sensors, row).total_length()
+        - beacons_on_row(&sensors, row) as isize;
 
     println!(
         "On row {}, there are {} positions where a beacon can not be present.",
@@ -94,10 +91,10 @@ fn main() {
 
     let mut frequency = 0;
 
-    for i in min..max {
-        let first_gap = find_first_gap_in_ranges(no_beacon_rows.get(&i).unwrap());
-        if first_gap.is_some() {
-            frequency = first_gap.unwrap() * 4000000 + i;
+    for i in min..=max {
+        let first_gap = find_no_beacon_spaces(&sensors, i).first_gap(min..max + 1);
+        if let Some(x) = first_gap {
+            frequency = x * 4000000 + i;
             break;
         }
     }
@@ -105,47 +102,23 @@ fn main() {
     println!("The distress frequency is {}.", frequency);
 }
 
-fn find_no_beacon_spaces(
-    sensors: &Vec<Sensor>,
-    min: isize,
-    max: isize,
-) -> HashMap<isize, Vec<(isize, isize)>> {
-    let mut results: HashMap<isize, Vec<(isize, isize)>> = HashMap::new();
-
-    for sensor in sensors {
-        for y in min.max(sensor.bounding_y_range.0)..max.min(sensor.bounding_y_range.1) {
-            let offset = y.abs_diff(sensor.y);
-
-            let x_results = results.entry(y).or_insert_with(|| Vec::new());
-            x_results.push((
-                // (sensor.bounding_x_range.0 + offset as isize).max(min),
-                // (sensor.bounding_x_range.1 - offset as isize).min(max),
-                sensor.bounding_x_range.0 + offset as isize,
-                sensor.bounding_x_range.1 - offset as isize,
-            ));
-        }
-    }
-
-    results
+/// The x positions on row `y` that are closer to a sensor than its beacon
+fn find_no_beacon_spaces(sensors: &[Sensor], y: isize) -> IntervalSet<isize> {
+    sensors
+        .iter()
+        .filter(|sensor| sensor.bounding_y_range.0 <= y && y <= sensor.bounding_y_range.1)
+        .map(|sensor| {
+            let offset = y.abs_diff(sensor.y) as isize;
+            sensor.bounding_x_range.0 + offset..sensor.bounding_x_range.1 - offset + 1
+        })
+        .collect()
 }
 
-fn find_first_gap_in_ranges(ranges: &Vec<(isize, isize)>) -> Option<isize> {
-    let start = ranges.iter().min_by_key(|r| r.0).unwrap().0;
-    let end = ranges.iter().max_by_key(|r| r.1).unwrap().1;
-
-    for i in start..end {
-        let mut found = false;
-        for r in ranges {
-            if i >= r.0 && i <= r.1 {
-                found = true;
-                break;
-            }
-        }
-
-        if !found {
-            return Some(i);
-        }
-    }
-
-    None
+fn beacons_on_row(sensors: &[Sensor], y: isize) -> usize {
+    sensors
+        .iter()
+        .filter(|sensor| sensor.closest_beacon.1 == y)
+        .map(|sensor| sensor.closest_beacon.0)
+        .collect::<HashSet<_>>()
+        .len()
 }
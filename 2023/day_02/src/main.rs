@@ -1,14 +1,13 @@
-use std::{
-    cmp,
-    io::{self, prelude::*},
-    str::FromStr,
-};
+use std::{cmp, error::Error};
 
+use aoc_common::{
+    input,
+    parse::{self, comma_list, unsigned},
+};
 use nom::{
     bytes::complete::tag,
-    character::complete::{alpha1, digit1, space1},
-    combinator::map,
-    combinator::map_res,
+    character::complete::{alpha1, space1},
+    combinator::map_opt,
     multi::separated_list1,
     sequence::separated_pair,
     IResult,
@@ -41,36 +40,9 @@ impl Round {
     }
 }
 
-#[derive(Debug)]
-struct ParseGameError {
-    // message: String,
-}
-
-impl From<nom::Err<nom::error::Error<&str>>> for ParseGameError {
-    fn from(_err: nom::Err<nom::error::Error<&str>>) -> Self {
-        ParseGameError {
-            // message: format!("Parsing error: {:?}", err),
-        }
-    }
-}
-
-impl FromStr for Game {
-    type Err = ParseGameError;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        parse_game(s)
-            .map(|(_, game)| game)
-            .map_err(ParseGameError::from)
-    }
-}
-
-fn parse_number(input: &str) -> IResult<&str, u32> {
-    map_res(digit1, str::parse)(input)
-}
-
 fn parse_game(input: &str) -> IResult<&str, Game> {
     let (input, _) = tag("Game ")(input)?;
-    let (input, game_number) = parse_number(input)?;
+    let (input, game_number) = unsigned(input)?;
     let (input, _) = tag(": ")(input)?;
     let (input, rounds) = separated_list1(tag("; "), parse_round)(input)?;
     Ok((
@@ -83,21 +55,21 @@ fn parse_game(input: &str) -> IResult<&str, Game> {
 }
 
 fn parse_round(input: &str) -> IResult<&str, Round> {
-    let (input, cube_count) = separated_list1(tag(", "), parse_cube_count)(input)?;
+    let (input, cube_count) = comma_list(parse_cube_count)(input)?;
     Ok((input, Round { cube_count }))
 }
 
 fn parse_cube_count(input: &str) -> IResult<&str, (Cube, u32)> {
-    let (input, (count, cube)) = separated_pair(parse_number, space1, parse_colour)(input)?;
+    let (input, (count, cube)) = separated_pair(unsigned, space1, parse_colour)(input)?;
     Ok((input, (cube, count)))
 }
 
 fn parse_colour(input: &str) -> IResult<&str, Cube> {
-    map(alpha1, |colour: &str| match colour {
-        "red" => Cube::Red,
-        "green" => Cube::Green,
-        "blue" => Cube::Blue,
-        _ => panic!("Invalid cube colour"),
+    map_opt(alpha1, |colour: &str| match colour {
+        "red" => Some(Cube::Red),
+        "green" => Some(Cube::Green),
+        "blue" => Some(Cube::Blue),
+        _ => None,
     })(input)
 }
 
@@ -186,14 +158,10 @@ fn get_fewest_possible_cubes(game: &Game) -> (u32, u32, u32) {
     (min_red, min_green, min_blue)
 }
 
-fn main() -> std::io::Result<()> {
-    let stdin = io::stdin();
-    let lines: Vec<String> = stdin.lock().lines().map(|l| l.unwrap()).collect();
+fn main() -> Result<(), Box<dyn Error>> {
+    let text = input::read(None)?;
 
-    let games = lines
-        .iter()
-        .map(|l| Game::from_str(l).unwrap())
-        .collect::<Vec<_>>();
+    let games = parse::parse_each_line(&text, parse_game)?;
     const MAX_RED: u32 = 12;
     const MAX_GREEN: u32 = 13;
     const MAX_BLUE: u32 = 14;
@@ -219,8 +187,11 @@ mod tests {
 
     #[test]
     fn test_parse_game() {
-        let game =
-            Game::from_str("Game 23: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green").unwrap();
+        let game = parse::parse_all(
+            "Game 23: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green",
+            parse_game,
+        )
+        .unwrap();
 
         assert_eq!(game.game_number, 23);
 
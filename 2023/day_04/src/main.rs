@@ -1,16 +1,13 @@
 use std::{
     collections::{HashMap, HashSet},
-    io::{self, prelude::*},
-    str::FromStr,
+    error::Error,
 };
 
-use nom::{
-    bytes::complete::tag,
-    character::complete::{digit1, space1},
-    combinator::map_res,
-    multi::separated_list1,
-    IResult,
+use aoc_common::{
+    input,
+    parse::{self, space_list, unsigned},
 };
+use nom::{bytes::complete::tag, character::complete::space1, IResult};
 
 #[derive(Debug)]
 struct Card {
@@ -41,52 +38,17 @@ impl Card {
     }
 }
 
-#[derive(Debug)]
-struct ParseCardError {
-    message: String,
-}
-
-impl std::fmt::Display for ParseCardError {
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        write!(f, "{}", self.message)
-    }
-}
-
-impl std::error::Error for ParseCardError {}
-
-impl From<nom::Err<nom::error::Error<&str>>> for ParseCardError {
-    fn from(err: nom::Err<nom::error::Error<&str>>) -> Self {
-        ParseCardError {
-            message: format!("Parsing error: {:?}", err),
-        }
-    }
-}
-
-impl FromStr for Card {
-    type Err = ParseCardError;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        parse_card(s)
-            .map(|(_, card)| card)
-            .map_err(ParseCardError::from)
-    }
-}
-
-fn parse_number(input: &str) -> IResult<&str, u32> {
-    map_res(digit1, str::parse)(input)
-}
-
 fn parse_card(input: &str) -> IResult<&str, Card> {
     let (input, _) = tag("Card")(input)?;
     let (input, _) = space1(input)?;
-    let (input, card_number) = parse_number(input)?;
+    let (input, card_number) = unsigned(input)?;
     let (input, _) = tag(":")(input)?;
     let (input, _) = space1(input)?;
-    let (input, winning_numbers) = separated_list1(space1, parse_number)(input)?;
+    let (input, winning_numbers) = space_list(unsigned)(input)?;
     let (input, _) = space1(input)?;
     let (input, _) = tag("|")(input)?;
     let (input, _) = space1(input)?;
-    let (input, scratched_numbers) = separated_list1(space1, parse_number)(input)?;
+    let (input, scratched_numbers) = space_list(unsigned)(input)?;
     Ok((
         input,
         Card {
@@ -121,14 +83,10 @@ fn count_all_cards(cards: &[Card]) -> u32 {
     card_counts.values().sum()
 }
 
-fn main() -> std::io::Result<()> {
-    let stdin = io::stdin();
-    let lines: Vec<String> = stdin.lock().lines().map(|l| l.unwrap()).collect();
+fn main() -> Result<(), Box<dyn Error>> {
+    let text = input::read(None)?;
 
-    let cards = lines
-        .iter()
-        .map(|l| Card::from_str(l).unwrap())
-        .collect::<Vec<_>>();
+    let cards = parse::parse_each_line(&text, parse_card)?;
 
     let points = calculate_total_points(&cards);
 
@@ -1,15 +1,14 @@
-use ranges::{GenericRange, Ranges};
-use std::{
-    io::{self, prelude::*},
-    str::FromStr,
-};
+use std::error::Error;
 
+use aoc_common::{
+    input,
+    interval::{IntervalSet, Shift},
+    parse::{self, blocks, labelled_block, space_list, unsigned},
+};
 use nom::{
     bytes::complete::tag,
-    character::complete::{alpha1, digit1, multispace1, newline, space1},
-    combinator::map_res,
-    multi::separated_list1,
-    sequence::pair,
+    character::complete::{alpha1, newline, space1},
+    sequence::{pair, separated_pair, terminated},
     IResult,
 };
 
@@ -24,13 +23,20 @@ struct Range {
 }
 impl Range {
     fn lookup(&self, val: u64) -> Option<u64> {
-        if val >= self.source_start && val <= self.source_start + self.length {
+        if val >= self.source_start && val < self.source_start + self.length {
             let ix = val - self.source_start;
             return Some(self.destination_start + ix);
         }
 
         None
     }
+
+    fn shift(&self) -> Shift<u64> {
+        Shift {
+            from: self.source_start..self.source_start + self.length,
+            to: self.destination_start,
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -76,63 +82,32 @@ impl Almanac {
             })
             .collect()
     }
-}
-
-#[derive(Debug)]
-struct ParseAlmanacError {
-    message: String,
-}
-
-impl std::fmt::Display for ParseAlmanacError {
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        write!(f, "{}", self.message)
-    }
-}
 
-impl std::error::Error for ParseAlmanacError {}
-
-impl From<nom::Err<nom::error::Error<&str>>> for ParseAlmanacError {
-    fn from(err: nom::Err<nom::error::Error<&str>>) -> Self {
-        ParseAlmanacError {
-            message: format!("Parsing error: {:?}", err),
+    /// Treats the seeds as pairs of start and length, and follows every seed
+    /// in those ranges at once
+    fn follow_seed_ranges(&self, start: &str, end: &str) -> IntervalSet<u64> {
+        let mut next_map = start;
+        let mut mapped: IntervalSet<u64> = self
+            .seeds
+            .chunks(2)
+            .map(|pair| pair[0].0..pair[0].0 + pair[1].0)
+            .collect();
+
+        while next_map != end {
+            let map = self.mappings.iter().find(|m| m.source == next_map).unwrap();
+            mapped = mapped.map_through(map.ranges.iter().map(Range::shift));
+            next_map = &map.destination;
         }
-    }
-}
 
-impl FromStr for Almanac {
-    type Err = ParseAlmanacError;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        parse_almanac(s)
-            .map(|(_, almanac)| almanac)
-            .map_err(ParseAlmanacError::from)
+        mapped
     }
 }
 
-fn parse_number(input: &str) -> IResult<&str, u64> {
-    map_res(digit1, str::parse)(input)
-}
-
 fn parse_almanac(input: &str) -> IResult<&str, Almanac> {
     let (input, _) = tag("seeds: ")(input)?;
-    let (input, seeds) = separated_list1(space1, parse_number)(input)?;
-    let (input, _) = pair(newline, newline)(input)?;
-    let (input, mappings) = separated_list1(pair(newline, newline), parse_mapping)(input)?;
-
-    Ok((
-        input,
-        Almanac {
-            seeds: seeds.iter().map(|&s| Seed(s)).collect(),
-            mappings,
-        },
-    ))
-}
-
-fn parse_almanac_as_seed_ranges(input: &str) -> IResult<&str, Almanac> {
-    let (input, _) = tag("seeds: ")(input)?;
-    let (input, seeds) = separated_list1(space1, parse_number)(input)?;
+    let (input, seeds) = space_list(unsigned)(input)?;
     let (input, _) = pair(newline, newline)(input)?;
-    let (input, mappings) = separated_list1(pair(newline, newline), parse_mapping)(input)?;
+    let (input, mappings) = blocks(parse_mapping)(input)?;
 
     Ok((
         input,
@@ -144,12 +119,8 @@ fn parse_almanac_as_seed_ranges(input: &str) -> IResult<&str, Almanac> {
 }
 
 fn parse_mapping(input: &str) -> IResult<&str, Mapping> {
-    let (input, source) = alpha1(input)?;
-    let (input, _) = tag("-to-")(input)?;
-    let (input, destination) = alpha1(input)?;
-    let (input, _) = tag(" map:")(input)?;
-    let (input, _) = newline(input)?;
-    let (input, ranges) = separated_list1(newline, parse_range)(input)?;
+    let label = terminated(separated_pair(alpha1, tag("-to-"), alpha1), tag(" map"));
+    let (input, ((source, destination), ranges)) = labelled_block(label, parse_range)(input)?;
 
     Ok((
         input,
@@ -162,11 +133,11 @@ fn parse_mapping(input: &str) -> IResult<&str, Mapping> {
 }
 
 fn parse_range(input: &str) -> IResult<&str, Range> {
-    let (input, destination_range_start) = parse_number(input)?;
+    let (input, destination_range_start) = unsigned(input)?;
     let (input, _) = space1(input)?;
-    let (input, source_range_start) = parse_number(input)?;
+    let (input, source_range_start) = unsigned(input)?;
     let (input, _) = space1(input)?;
-    let (input, range_length) = parse_number(input)?;
+    let (input, range_length) = unsigned(input)?;
 
     Ok((
         input,
@@ -178,47 +149,49 @@ fn parse_range(input: &str) -> IResult<&str, Range> {
     ))
 }
 
-fn main() -> std::io::Result<()> {
-    let stdin = io::stdin();
-    let mut buf = String::new();
-    stdin.lock().read_to_string(&mut buf).unwrap();
+fn main() -> Result<(), Box<dyn Error>> {
+    let buf = input::read(None)?;
 
-    let almanac = Almanac::from_str(&buf).unwrap();
+    let almanac = parse::parse_all(&buf, parse_almanac)?;
 
-    dbg!(&almanac);
+    let locations = almanac.follow_lookups("seed", "location");
 
-    for i in 0..=100 {
-        print!("{i}");
-        let mut chained_val = i;
+    let location = locations.iter().min().unwrap();
 
-        for mapping in &almanac.mappings {
-            let i2 = mapping.lookup(chained_val);
-            chained_val = i2;
-            print! {" {i2}"};
-        }
+    println!("Should plant in location {location}.");
 
-        println!("");
+    let locations = almanac.follow_seed_ranges("seed", "location");
 
-        // let i2 = almanac.mappings[0].lookup(i);
-        // println!("{i} {i2}");
+    if let Some(location) = locations.span() {
+        println!("Should plant seed ranges in location {}.", location.start);
     }
 
-    let mut ranges = Vec::new();
+    Ok(())
+}
 
-    for mapping in &almanac.mappings {
-        // ranges.push((mapping.ran)
-        for range in &mapping.ranges {
-            ranges.push((range.source_start, range.source_start + range.length));
-        }
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lookup_end_of_range() {
+        // seed-to-soil 50 98 2 maps 98 and 99, and nothing past them
+        let range = Range {
+            source_start: 98,
+            destination_start: 50,
+            length: 2,
+        };
+
+        assert_eq!(range.lookup(97), None);
+        assert_eq!(range.lookup(98), Some(50));
+        assert_eq!(range.lookup(99), Some(51));
+        assert_eq!(range.lookup(100), None);
+
+        let mapping = Mapping {
+            source: "seed".to_string(),
+            destination: "soil".to_string(),
+            ranges: vec![range],
+        };
+        assert_eq!(mapping.lookup(100), 100);
     }
-
-    // dbg!(&ranges);
-
-    let locations = almanac.follow_lookups("seed", "location");
-
-    let location = locations.iter().min().unwrap();
-
-    println!("Should plant in location {location}.");
-
-    Ok(())
 }
@@ -14,7 +14,7 @@ fn test_example() -> Result<(), Box<dyn std::error::Error>> {
     file.read_to_string(&mut contents)?;
 
     let assert = cmd.write_stdin(contents).assert();
-    assert.stdout("Should plant in location 35.\n");
+    assert.stdout("Should plant in location 35.\nShould plant seed ranges in location 46.\n");
 
     Ok(())
 }
@@ -1,12 +1,10 @@
-use std::io::{self, prelude::*};
-
-use nom::{
-    bytes::complete::tag,
-    character::complete::{digit1, newline, space1},
-    combinator::map_res,
-    multi::separated_list1,
-    IResult,
+use std::error::Error;
+
+use aoc_common::{
+    input,
+    parse::{self, key_value, space_list, unsigned},
 };
+use nom::{bytes::complete::tag, character::complete::newline, IResult};
 
 #[derive(Debug)]
 struct Race {
@@ -14,22 +12,14 @@ struct Race {
     record: u64,
 }
 
-fn parse_number(input: &str) -> IResult<&str, u64> {
-    map_res(digit1, str::parse)(input)
-}
-
 fn parse_times(input: &str) -> IResult<&str, Vec<u64>> {
-    let (input, _) = tag("Time:")(input)?;
-    let (input, _) = space1(input)?;
-    let (input, times) = separated_list1(space1, parse_number)(input)?;
+    let (input, (_, times)) = key_value(tag("Time"), space_list(unsigned))(input)?;
 
     Ok((input, times))
 }
 
 fn parse_distance_records(input: &str) -> IResult<&str, Vec<u64>> {
-    let (input, _) = tag("Distance:")(input)?;
-    let (input, _) = space1(input)?;
-    let (input, distances) = separated_list1(space1, parse_number)(input)?;
+    let (input, (_, distances)) = key_value(tag("Distance"), space_list(unsigned))(input)?;
 
     Ok((input, distances))
 }
@@ -96,18 +86,16 @@ fn count_record_beaters(race: &Race) -> u64 {
     vals as u64
 }
 
-fn main() -> std::io::Result<()> {
-    let stdin = io::stdin();
-    let mut buf = String::new();
-    stdin.lock().read_to_string(&mut buf).unwrap();
+fn main() -> Result<(), Box<dyn Error>> {
+    let buf = input::read(None)?;
 
-    let (_, races) = parse_races(&buf).unwrap();
+    let races = parse::parse_all(&buf, parse_races)?;
 
     let record_product: u64 = races.iter().map(count_record_beaters).product();
 
     println!("Multiplying the ways of winning together makes {record_product}.");
 
-    let (_, race) = parse_races_v2(&buf).unwrap();
+    let race = parse::parse_all(&buf, parse_races_v2)?;
 
     let record2 = count_record_beaters(&race);
 
@@ -1,12 +1,12 @@
-use std::{
-    collections::HashMap,
-    io::{self, prelude::*},
-};
+use std::{collections::HashMap, error::Error};
 
+use aoc_common::{
+    input, math,
+    parse::{self, lines},
+};
 use nom::{
     bytes::complete::tag,
     character::complete::{alpha1, alphanumeric1, newline},
-    multi::separated_list1,
     IResult,
 };
 
@@ -75,7 +75,7 @@ fn parse_map(input: &str) -> IResult<&str, Map> {
     let (input, directions) = parse_directions(input)?;
     let (input, _) = newline(input)?;
     let (input, _) = newline(input)?;
-    let (input, locations) = separated_list1(newline, parse_location)(input)?;
+    let (input, locations) = lines(parse_location)(input)?;
 
     Ok((
         input,
@@ -121,64 +121,43 @@ fn count_steps(map: &Map, start: &str, end: &str) -> Option<usize> {
     Some(visited_locations.len())
 }
 
+/// Each ghost's path comes back round to its Z in as many steps as it took to
+/// get there the first time, so they all reach theirs together at the lcm
 fn count_ghost_steps(map: &Map, start: &str, end: &str) -> Option<usize> {
     let location_lookup: HashMap<String, &Location> =
         HashMap::from_iter(map.locations.iter().map(|l| (l.name.clone(), l)));
 
-    let mut location_names = map
-        .locations
-        .iter()
-        .filter(|l| l.name.ends_with(&start))
-        .map(|l| &l.name)
-        .collect::<Vec<_>>();
-
-    let mut count = 0;
-
-    for dir in map.directions.iter().cycle() {
-        if location_names.iter().all(|l| l.ends_with(&end)) {
-            break;
-        }
-
-        count += 1;
+    let mut loop_lengths = Vec::new();
 
-        let mut next_locations = Vec::new();
+    for ghost in map.locations.iter().filter(|l| l.name.ends_with(start)) {
+        let mut location = ghost;
+        let mut count = 0;
 
-        for &location_name in &location_names {
-            // if location_name == end {
-            //     break;
-            // }
-
-            let location = location_lookup.get(location_name);
-
-            if location.is_none() {
-                return None;
+        for dir in map.directions.iter().cycle() {
+            if location.name.ends_with(end) {
+                break;
             }
 
-            let location = *location.unwrap();
-
-            if *dir == Direction::Left {
-                next_locations.push(&location.left);
+            let next = if *dir == Direction::Left {
+                &location.left
             } else {
-                next_locations.push(&location.right);
-            }
+                &location.right
+            };
+
+            location = location_lookup.get(next)?;
+            count += 1;
         }
 
-        location_names = next_locations;
+        loop_lengths.push(count);
     }
 
-    // dbg!(&visited_locations);
-
-    Some(count)
-
-    // Some(visited_locations.len() / location_names.len())
+    Some(math::lcm_all(loop_lengths))
 }
 
-fn main() -> std::io::Result<()> {
-    let stdin = io::stdin();
-    let mut buf = String::new();
-    stdin.lock().read_to_string(&mut buf).unwrap();
+fn main() -> Result<(), Box<dyn Error>> {
+    let buf = input::read(None)?;
 
-    let (_, map) = parse_map(&buf).unwrap();
+    let map = parse::parse_all(&buf, parse_map)?;
 
     // dbg!(&map);
 
@@ -14,9 +14,7 @@ fn test_example() -> Result<(), Box<dyn std::error::Error>> {
     file.read_to_string(&mut contents)?;
 
     let assert = cmd.write_stdin(contents).assert();
-    assert.stdout(
-        "There are 2 steps to reach ZZZ.\nThere is no way for ghosts to reach all the Zs.\n",
-    );
+    assert.stdout("There are 2 steps to reach ZZZ.\nGhosts take 2 steps to reach all the Zs.\n");
 
     Ok(())
 }
@@ -1,12 +1,12 @@
 #![allow(clippy::needless_range_loop)]
 
-use std::collections::HashMap;
 use std::hash::{Hash, Hasher};
 use std::{
     collections::hash_map::DefaultHasher,
     io::{self, prelude::*},
 };
 
+use aoc_common::cycle;
 use bitmaps::Bitmap;
 
 #[derive(Debug, PartialEq, Clone, Hash)]
@@ -221,35 +221,18 @@ fn main() -> std::io::Result<()> {
         Direction::East,
     ];
 
-    let mut count = 0;
-    let mut seen_states: HashMap<u64, usize> = HashMap::new();
     const MAX_COUNT: usize = 1000000000;
 
-    while count < MAX_COUNT {
-        let state_hash = map.to_hash();
-        if let Some(&first_seen) = seen_states.get(&state_hash) {
-            // Cycle detected
-            let cycle_length = count - first_seen;
-            let remaining_cycles = (MAX_COUNT - count) / cycle_length;
-            count += remaining_cycles * cycle_length;
-            break;
-        } else {
-            seen_states.insert(state_hash, count);
-        }
-
-        for &direction in &directions {
-            map.tilt(direction);
-        }
-
-        count += 1;
-    }
-
-    let remaining_iterations = MAX_COUNT - count;
-    for _ in 0..remaining_iterations {
-        for &direction in &directions {
-            map.tilt(direction);
-        }
-    }
+    let (map, _) = cycle::state_after(
+        map,
+        MAX_COUNT,
+        |map| {
+            for &direction in &directions {
+                map.tilt(direction);
+            }
+        },
+        Map::to_hash,
+    );
 
     // map.draw();
 
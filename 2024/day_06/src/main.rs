@@ -186,9 +186,6 @@ fn detect_guard_looping(guard: &mut Guard) -> bool {
 
         guard.make_one_step();
     }
-
-    // < 1567
-    // == 1562
 }
 
 fn generate_all_maps(base_map: &Map, obstacle_positions: &[Point]) -> Vec<Map> {
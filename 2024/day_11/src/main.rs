@@ -3,6 +3,8 @@ use std::{
     io::{self, prelude::*},
 };
 
+use aoc_common::memo::Memo;
+
 fn split_number(n: Stone) -> (Stone, Stone) {
     let len = ((n as f64).log10() as usize) + 1;
     let half_len = len / 2;
@@ -35,28 +37,20 @@ fn blink_stone(n: Stone) -> Vec<Stone> {
 type Stone = usize;
 
 struct CachedCounter {
-    blink_cache: HashMap<Stone, Vec<Stone>>,
+    blink_cache: Memo<Stone, Vec<Stone>>,
     stone_counts: HashMap<Stone, usize>,
 }
 
 impl CachedCounter {
     fn new() -> Self {
         CachedCounter {
-            blink_cache: HashMap::new(),
+            blink_cache: Memo::new(),
             stone_counts: HashMap::new(),
         }
     }
 
     fn cached_blink_stone(&mut self, n: Stone) -> Vec<Stone> {
-        if let Some(lookup) = self.blink_cache.get(&n) {
-            return lookup.clone();
-        }
-
-        let answer = blink_stone(n);
-
-        self.blink_cache.insert(n, answer.clone());
-
-        answer
+        self.blink_cache.get_or_insert_with(n, |_| blink_stone(n))
     }
 
     fn naive_blink(&mut self, stones: &[Stone]) -> Vec<Stone> {
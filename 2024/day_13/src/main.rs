@@ -1,3 +1,4 @@
+use aoc_common::math;
 use regex::Regex;
 use std::io::{self, prelude::*};
 
@@ -68,65 +69,24 @@ fn parse(lines: &[String]) -> Vec<Machine> {
         .collect()
 }
 
-type Var = isize;
-type Target = isize;
-type Equation = (Var, Var, Target);
-type Answer = (Var, Var);
-
-fn solve(eq1: Equation, eq2: Equation) -> Option<Answer> {
-    // Multiply both sides by the other's A
-    let other_a = eq2.0;
-    let new_eq1 = (eq1.0 * other_a, eq1.1 * other_a, eq1.2 * other_a);
-
-    let other_a = eq1.0;
-    let new_eq2 = (eq2.0 * other_a, eq2.1 * other_a, eq2.2 * other_a);
-
-    // Sub one from the other to eliminate A
-    let eliminated_a_eq = (
-        new_eq2.0 - new_eq1.0,
-        new_eq2.1 - new_eq1.1,
-        new_eq2.2 - new_eq1.2,
-    );
-
-    assert!(eliminated_a_eq.0 == 0);
-
-    // println!("Eq {:?}", eliminated_a_eq);
-
-    // Now can solve for B and A
-    let b = eliminated_a_eq.2 / eliminated_a_eq.1;
-    let a = (eq1.2 - (eq1.1 * b)) / eq1.0;
-
-    // Check the other one
-    if eq2.0 * a + eq2.1 * b == eq2.2 {
-        return Some((a, b));
-    }
-
-    None
-}
-
 fn count_tokens_for_machine(machine: &Machine) -> Option<usize> {
-    let eq1 = (
-        machine.button_a.0,
-        machine.button_b.0,
-        machine.prize_position.0,
+    let presses = math::solve_linear(
+        &[
+            vec![machine.button_a.0 as i64, machine.button_b.0 as i64],
+            vec![machine.button_a.1 as i64, machine.button_b.1 as i64],
+        ],
+        &[
+            machine.prize_position.0 as i64,
+            machine.prize_position.1 as i64,
+        ],
     );
-    let eq2 = (
-        machine.button_a.1,
-        machine.button_b.1,
-        machine.prize_position.1,
-    );
-
-    // println!("EQ1 {:?}", eq1);
-    // println!("EQ2 {:?}", eq2);
-
-    const A_TOKEN_COST: isize = 3;
-    const B_TOKEN_COST: isize = 1;
-
-    let solved = solve(eq1, eq2);
 
-    // println!("solved {:?}", solved);
+    const A_TOKEN_COST: u64 = 3;
+    const B_TOKEN_COST: u64 = 1;
 
-    solved.map(|solved| (solved.0 * A_TOKEN_COST + solved.1 * B_TOKEN_COST) as usize)
+    presses
+        .naturals()
+        .map(|presses| (presses[0] * A_TOKEN_COST + presses[1] * B_TOKEN_COST) as usize)
 }
 
 fn count_tokens_for_all_machine(machines: &[Machine]) -> usize {
@@ -0,0 +1,298 @@
+use aoc_common::{Answer, Solution};
+use std::collections::HashSet;
+
+type Point = (isize, isize);
+
+#[derive(Debug, Clone)]
+pub struct Map {
+    data: Vec<char>,
+    width: usize,
+    height: usize,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Direction {
+    fn to_point(self) -> Point {
+        match self {
+            Direction::Up => (0, -1),
+            Direction::Down => (0, 1),
+            Direction::Left => (-1, 0),
+            Direction::Right => (1, 0),
+        }
+    }
+
+    fn from(c: char) -> Direction {
+        match c {
+            '<' => Direction::Left,
+            '>' => Direction::Right,
+            '^' => Direction::Up,
+            'v' => Direction::Down,
+            _ => panic!("Cannot parse"),
+        }
+    }
+}
+
+impl Map {
+    fn new(data: Vec<char>, width: usize, height: usize) -> Map {
+        Map {
+            data,
+            width,
+            height,
+        }
+    }
+
+    fn in_bounds(&self, x: isize, y: isize) -> bool {
+        x >= 0 && y >= 0 && x < self.width as isize && y < self.height as isize
+    }
+
+    fn get_char_at(&self, x: isize, y: isize) -> Option<char> {
+        if !self.in_bounds(x, y) {
+            return None;
+        }
+
+        self.data
+            .get((x as usize) + (y as usize) * self.width)
+            .copied()
+    }
+
+    fn find_first_position(&self, needle: char) -> Option<(isize, isize)> {
+        let ix = self.data.iter().position(|c| *c == needle);
+
+        // println!("IX {:?}", ix);
+
+        ix.map(|ix| {
+            (
+                ix as isize % self.width as isize,
+                ix as isize / self.width as isize,
+            )
+        })
+    }
+
+    fn is_wall(c: char) -> bool {
+        c == '#'
+    }
+
+    fn is_wall_on_tile(&self, x: isize, y: isize) -> bool {
+        let c = self.get_char_at(x, y);
+
+        Map::is_wall(c.unwrap())
+    }
+
+    fn is_space(c: char) -> bool {
+        c == '.'
+    }
+
+    fn is_space_on_tile(&self, x: isize, y: isize) -> bool {
+        let c = self.get_char_at(x, y);
+
+        Map::is_space(c.unwrap())
+    }
+
+    fn is_barrel(c: char) -> bool {
+        c == 'O' || c == '[' || c == ']'
+    }
+
+    fn is_barrel_on_tile(&self, x: isize, y: isize) -> bool {
+        // println!("xy {} {} ", x, y);
+        let c = self.get_char_at(x, y);
+
+        Map::is_barrel(c.unwrap())
+    }
+
+    fn is_scoring_barrel(c: char) -> bool {
+        c == 'O' || c == '['
+    }
+
+    fn is_scoring_barrel_on_tile(&self, x: isize, y: isize) -> bool {
+        // println!("xy {} {} ", x, y);
+        let c = self.get_char_at(x, y);
+
+        Map::is_scoring_barrel(c.unwrap())
+    }
+
+    fn set_char_at(&mut self, x: isize, y: isize, c: char) {
+        if !self.in_bounds(x, y) {
+            return;
+        }
+
+        self.data[(x as usize) + (y as usize) * self.width] = c;
+    }
+
+    /// Pushes the barrel on the tile and everything stacked behind it, a wide
+    /// barrel pushed up or down taking whatever is behind both its halves
+    fn push_barrel(&mut self, x: isize, y: isize, dir: Direction) -> bool {
+        let (dx, dy) = dir.to_point();
+        let mut moving = Vec::new();
+        let mut seen = HashSet::new();
+        let mut frontier = vec![(x, y)];
+
+        while let Some((x, y)) = frontier.pop() {
+            if !seen.insert((x, y)) {
+                continue;
+            }
+
+            let c = self.get_char_at(x, y).unwrap();
+
+            if Map::is_wall(c) {
+                return false;
+            }
+
+            if Map::is_space(c) {
+                continue;
+            }
+
+            moving.push((x, y));
+            frontier.push((x + dx, y + dy));
+
+            if dy != 0 && c == '[' {
+                frontier.push((x + 1, y));
+            } else if dy != 0 && c == ']' {
+                frontier.push((x - 1, y));
+            }
+        }
+
+        // Move the furthest tiles first so nothing is overwritten
+        moving.sort_by_key(|(x, y)| -(x * dx + y * dy));
+
+        for (x, y) in moving {
+            let c = self.get_char_at(x, y).unwrap();
+            self.set_char_at(x + dx, y + dy, c);
+            self.set_char_at(x, y, '.');
+        }
+
+        true
+    }
+
+    fn double(&self) -> Map {
+        let new_map_data = self
+            .data
+            .iter()
+            .flat_map(|c| match c {
+                '#' => ['#', '#'],
+                'O' => ['[', ']'],
+                '.' => ['.', '.'],
+                '@' => ['@', '.'],
+                _ => panic!("Not sure"),
+            })
+            .collect();
+
+        Map::new(new_map_data, self.width * 2, self.height)
+    }
+}
+
+fn parse(lines: &[String]) -> (Map, Vec<Direction>) {
+    let (map_lines, dir_lines) = lines.split_at(lines.iter().position(|l| l.is_empty()).unwrap());
+
+    let grid_data = map_lines
+        .iter()
+        .flat_map(|l| l.chars().collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+
+    let height = map_lines.len();
+    let width = map_lines[0].len();
+
+    let map = Map::new(grid_data, width, height);
+
+    let instructions = dir_lines
+        .iter()
+        .flat_map(|l| l.chars().map(Direction::from))
+        .collect();
+
+    (map, instructions)
+}
+
+struct Robot {
+    position: Point,
+    map: Map,
+}
+
+impl Robot {
+    fn new(mut map: Map) -> Self {
+        // println!("{:?}", map);
+
+        let start_position = map
+            .find_first_position('@')
+            .expect("Cannot find start position");
+
+        let start_position = (start_position.0, start_position.1);
+
+        map.set_char_at(start_position.0, start_position.1, '.');
+
+        Robot {
+            map,
+            position: start_position,
+        }
+    }
+
+    fn walk(&mut self, dir: Direction) {
+        let dir_vector = dir.to_point();
+        let target_tile = (
+            self.position.0 + dir_vector.0,
+            self.position.1 + dir_vector.1,
+        );
+
+        if self.map.is_wall_on_tile(target_tile.0, target_tile.1) {
+            return;
+        }
+
+        if self.map.is_space_on_tile(target_tile.0, target_tile.1)
+            || (self.map.is_barrel_on_tile(target_tile.0, target_tile.1)
+                && self.map.push_barrel(target_tile.0, target_tile.1, dir))
+        {
+            self.position = target_tile;
+        }
+    }
+}
+
+fn follow_instructions(robot: &mut Robot, instructions: &[Direction]) {
+    instructions.iter().for_each(|&i| robot.walk(i));
+}
+
+fn get_gps(map: &Map) -> usize {
+    map.data
+        .iter()
+        .enumerate()
+        .map(|(p, _)| {
+            let x = p % map.width;
+            let y = p / map.width;
+
+            if map.is_scoring_barrel_on_tile(x as isize, y as isize) {
+                return 100 * y + x;
+            }
+
+            0
+        })
+        .sum()
+}
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    type Input = (Map, Vec<Direction>);
+
+    fn parse(input: &str) -> Self::Input {
+        let lines: Vec<String> = input.lines().map(String::from).collect();
+        parse(&lines)
+    }
+
+    fn part1((map, instructions): &Self::Input) -> Answer {
+        let mut robot = Robot::new(map.clone());
+        follow_instructions(&mut robot, instructions);
+
+        get_gps(&robot.map).into()
+    }
+
+    fn part2((map, instructions): &Self::Input) -> Answer {
+        let mut robot = Robot::new(map.double());
+        follow_instructions(&mut robot, instructions);
+
+        get_gps(&robot.map).into()
+    }
+}
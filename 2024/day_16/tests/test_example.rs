@@ -14,7 +14,7 @@ fn test_example() -> Result<(), Box<dyn std::error::Error>> {
     file.read_to_string(&mut contents)?;
 
     let assert = cmd.write_stdin(contents).assert();
-    assert.stdout("The lowest score a reindeer can get is 7036\n");
+    assert.stdout("The lowest score a reindeer can get is 7036\nThere are 45 tiles on at least one of the best paths\n");
 
     Ok(())
 }
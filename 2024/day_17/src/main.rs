@@ -278,5 +278,3 @@ fn main() -> std::io::Result<()> {
 
     Ok(())
 }
-
-// !4,3,0,5,1,2,1,5,2
@@ -1,7 +1,6 @@
-use std::{
-    collections::HashMap,
-    io::{self, prelude::*},
-};
+use std::io::{self, prelude::*};
+
+use aoc_common::memo::Memo;
 
 type Towel = String;
 type Pattern = String;
@@ -33,84 +32,41 @@ fn parse(lines: &[String]) -> (Vec<Towel>, Vec<Pattern>) {
 // }
 
 fn find_possible_patterns_cached(towels: &[Towel], patterns: &[Pattern]) -> Vec<Pattern> {
-    let mut cache = Cache::new();
+    let mut cache = Memo::new();
 
     patterns
         .iter()
-        .filter(|p| cache.is_pattern_possible(towels, p))
+        .filter(|p| {
+            cache.recurse(p.to_string(), &|is_possible, pattern: String| {
+                pattern.is_empty()
+                    || towels.iter().any(|t| {
+                        pattern.starts_with(t) && is_possible(pattern[t.len()..].to_string())
+                    })
+            })
+        })
         .cloned()
         .collect()
 }
 
 fn find_all_possible_pattern_combos_cached(towels: &[Towel], patterns: &[Pattern]) -> usize {
-    let mut cache = Cache::new();
+    let mut cache = Memo::new();
 
     patterns
         .iter()
-        .map(|p| cache.all_combinations_possible_cached(towels, p))
-        .sum()
-}
-
-struct Cache {
-    cached_pattern: HashMap<String, bool>,
-    cached_pattern_count: HashMap<String, usize>,
-}
-
-impl Cache {
-    fn new() -> Cache {
-        Cache {
-            cached_pattern: HashMap::new(),
-            cached_pattern_count: HashMap::new(),
-        }
-    }
-
-    fn is_pattern_possible(&mut self, towels: &[Towel], pattern: &str) -> bool {
-        if self.cached_pattern.contains_key(pattern) {
-            return self.cached_pattern[pattern];
-        }
-
-        let result = self._is_pattern_possible_cached(towels, pattern);
-
-        self.cached_pattern.insert(pattern.to_string(), result);
-
-        result
-    }
-
-    fn _is_pattern_possible_cached(&mut self, towels: &[Towel], pattern: &str) -> bool {
-        if pattern.is_empty() {
-            return true;
-        }
-
-        // println!("Pattern {}", pattern);
-
-        towels.iter().any(|t| {
-            pattern.starts_with(t) && self.is_pattern_possible(towels, &pattern[t.len()..])
+        .map(|p| {
+            cache.recurse(p.to_string(), &|count, pattern: String| {
+                if pattern.is_empty() {
+                    return 1;
+                }
+
+                towels
+                    .iter()
+                    .filter(|t| pattern.starts_with(t.as_str()))
+                    .map(|t| count(pattern[t.len()..].to_string()))
+                    .sum()
+            })
         })
-    }
-
-    fn all_combinations_possible_cached(&mut self, towels: &[Towel], pattern: &str) -> usize {
-        // println!("Pattern {}", pattern);
-
-        if self.cached_pattern_count.contains_key(pattern) {
-            return self.cached_pattern_count[pattern];
-        }
-
-        if pattern.is_empty() {
-            return 1;
-        }
-
-        let mut count = 0;
-
-        for t in towels {
-            if pattern.starts_with(t) {
-                count += self.all_combinations_possible_cached(towels, &pattern[t.len()..]);
-            }
-        }
-
-        self.cached_pattern_count.insert(pattern.to_string(), count);
-
-        count
-    }
+        .sum()
 }
 
 fn main() -> std::io::Result<()> {
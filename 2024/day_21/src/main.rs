@@ -259,8 +259,3 @@ fn main() -> std::io::Result<()> {
 
     Ok(())
 }
-
-// < 235218
-// < 226566
-// < 233106
-// ! 225994
@@ -127,5 +127,3 @@ fn main() -> std::io::Result<()> {
 
     Ok(())
 }
-
-// < 1848
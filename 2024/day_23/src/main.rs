@@ -149,6 +149,3 @@ fn main() -> std::io::Result<()> {
 
     Ok(())
 }
-
-// !74
-// < 2462
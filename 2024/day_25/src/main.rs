@@ -1,63 +1,31 @@
-use std::io::{self, prelude::*};
+use aoc_common::input::{self, Block, InputError};
 
 type Pin = usize;
 type Key = Vec<Pin>;
 type Lock = Vec<Pin>;
 
-#[derive(Debug)]
-enum ParseType {
-    Key,
-    Lock,
-}
-
-fn parse(lines: &[String]) -> (Vec<Key>, Vec<Lock>) {
-    let mut working_item: Option<ParseType> = None;
-    let mut keys = Vec::new();
-    let mut locks = Vec::new();
-    let mut current_pin_count = vec![0, 0, 0, 0, 0];
-
-    for (ix, l) in lines.iter().enumerate() {
-        // println!("{:?}", l);
-        // println!("Working item is {:?}", working_item);
-
-        if working_item.is_none() {
-            if l.starts_with('#') {
-                // println!("It's a lock");
-                working_item = Some(ParseType::Lock);
-                continue;
-            }
-
-            if l.starts_with('.') {
-                // println!("It's a key");
-
-                working_item = Some(ParseType::Key);
-                continue;
-            }
-        }
-
-        // println!("CHecking these");
-
-        for (ix, c) in l.chars().enumerate() {
+/// Counts the pins in each column of a block, leaving out the full row at the
+/// top of a lock or the bottom of a key
+fn heights(block: &Block) -> Vec<Pin> {
+    let mut heights: Vec<Pin> = vec![0; block.lines[0].len()];
+    for line in &block.lines {
+        for (ix, c) in line.chars().enumerate() {
             if c == '#' {
-                current_pin_count[ix] += 1;
+                heights[ix] += 1;
             }
         }
+    }
+    heights.iter().map(|h| h.saturating_sub(1)).collect()
+}
 
-        if l.is_empty() || l.starts_with(' ') || ix == lines.len() - 1 {
-            // println!("Done - {} {:?}", l, current_pin_count);
-
-            match working_item {
-                Some(ParseType::Key) => {
-                    current_pin_count.iter_mut().for_each(|c| *c -= 1);
-                    keys.push(current_pin_count.clone());
-                }
-                Some(ParseType::Lock) => locks.push(current_pin_count.clone()),
-                _ => (),
-            }
+fn parse(text: &str) -> (Vec<Key>, Vec<Lock>) {
+    let mut keys = Vec::new();
+    let mut locks = Vec::new();
 
-            working_item = None;
-            current_pin_count = vec![0, 0, 0, 0, 0];
-            continue;
+    for block in input::blocks(text) {
+        match block.lines[0].starts_with('#') {
+            true => locks.push(heights(&block)),
+            false => keys.push(heights(&block)),
         }
     }
 
@@ -78,11 +46,10 @@ fn find_fitting_keys(keys: &[Key], locks: &[Lock]) -> Vec<Key> {
     fitting_keys
 }
 
-fn main() -> std::io::Result<()> {
-    let stdin = io::stdin();
-    let lines: Vec<String> = stdin.lock().lines().map(|l| l.unwrap()).collect();
+fn main() -> Result<(), InputError> {
+    let text = input::read(None)?;
 
-    let (keys, locks) = parse(&lines);
+    let (keys, locks) = parse(&text);
 
     let fitting_keys = find_fitting_keys(&keys, &locks);
 
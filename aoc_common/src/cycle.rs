@@ -0,0 +1,298 @@
+//! Skipping ahead in puzzles that ask for the state after a billion steps, by
+//! finding where the states start repeating.
+//!
+//! Each state is compared by a key taken from it, so a state can carry things
+//! that keep changing, like a step count, as long as the key leaves them out.
+//! Those parts of a skipped-to state are whatever they were at the step it
+//! stands in for.
+
+use std::collections::hash_map::{Entry, HashMap};
+use std::hash::Hash;
+
+/// `prefix` steps lead into a loop that comes back round every `period` steps
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    pub prefix: usize,
+    pub period: usize,
+}
+
+impl Cycle {
+    /// The earliest step with the same state as step `n`
+    pub fn equivalent(&self, n: usize) -> usize {
+        match n < self.prefix {
+            true => n,
+            false => self.prefix + (n - self.prefix) % self.period,
+        }
+    }
+
+    /// How many whole times round the loop `n` steps go
+    pub fn laps(&self, n: usize) -> usize {
+        n.saturating_sub(self.prefix) / self.period
+    }
+}
+
+/// Where stepping stopped, either at the first repeated state, `at` steps in,
+/// or having taken every step asked for without a repeat
+enum Walk<S> {
+    Looped { state: S, cycle: Cycle, at: usize },
+    Finished(S),
+}
+
+/// Steps until a key comes round again, remembering every key on the way
+fn walk<S, K: Eq + Hash>(
+    mut state: S,
+    limit: usize,
+    mut step: impl FnMut(&mut S),
+    mut key: impl FnMut(&S) -> K,
+) -> Walk<S> {
+    let mut seen = HashMap::new();
+
+    for at in 0..limit {
+        match seen.entry(key(&state)) {
+            Entry::Occupied(first) => {
+                let cycle = Cycle {
+                    prefix: *first.get(),
+                    period: at - first.get(),
+                };
+                return Walk::Looped { state, cycle, at };
+            }
+            Entry::Vacant(entry) => {
+                entry.insert(at);
+            }
+        }
+        step(&mut state);
+    }
+
+    Walk::Finished(state)
+}
+
+/// Finds the cycle, remembering the key of every state until one repeats.
+/// Never returns if the states never repeat.
+pub fn find<S: Clone, K: Eq + Hash>(
+    initial: &S,
+    step: impl FnMut(&mut S),
+    key: impl FnMut(&S) -> K,
+) -> Cycle {
+    match walk(initial.clone(), usize::MAX, step, key) {
+        Walk::Looped { cycle, .. } => cycle,
+        Walk::Finished(_) => unreachable!("Ran out of steps before the states repeated"),
+    }
+}
+
+/// The state after `n` steps, skipping the laps of the loop once one shows
+/// up, along with the cycle when there was one before step `n`
+pub fn state_after<S, K: Eq + Hash>(
+    initial: S,
+    n: usize,
+    mut step: impl FnMut(&mut S),
+    key: impl FnMut(&S) -> K,
+) -> (S, Option<Cycle>) {
+    match walk(initial, n, &mut step, key) {
+        Walk::Looped {
+            mut state,
+            cycle,
+            at,
+        } => {
+            for _ in 0..(n - at) % cycle.period {
+                step(&mut state);
+            }
+            (state, Some(cycle))
+        }
+        Walk::Finished(state) => (state, None),
+    }
+}
+
+/// Brent's algorithm, which only ever holds two states and their keys. Gives
+/// back the state after `limit` steps instead if the loop has not been found
+/// by then.
+fn brent_within<S: Clone, K: PartialEq>(
+    initial: &S,
+    limit: usize,
+    mut step: impl FnMut(&mut S),
+    mut key: impl FnMut(&S) -> K,
+) -> Result<Cycle, S> {
+    if limit == 0 {
+        return Err(initial.clone());
+    }
+
+    // The hare runs ahead in ever longer stretches, and the tortoise waits at
+    // the start of each stretch until the hare comes back round to it
+    let mut tortoise = key(initial);
+    let mut hare = initial.clone();
+    step(&mut hare);
+    let mut taken = 1;
+    let mut power = 1;
+    let mut period = 1;
+
+    while key(&hare) != tortoise {
+        if taken == limit {
+            return Err(hare);
+        }
+        if power == period {
+            tortoise = key(&hare);
+            power *= 2;
+            period = 0;
+        }
+        step(&mut hare);
+        taken += 1;
+        period += 1;
+    }
+
+    // With the hare a whole loop ahead, they meet where the loop starts
+    let mut tortoise = initial.clone();
+    let mut hare = initial.clone();
+    for _ in 0..period {
+        step(&mut hare);
+    }
+    let mut prefix = 0;
+    while key(&tortoise) != key(&hare) {
+        step(&mut tortoise);
+        step(&mut hare);
+        prefix += 1;
+    }
+
+    Ok(Cycle { prefix, period })
+}
+
+/// Finds the cycle with Brent's algorithm, for states too big to remember
+/// them all. Never returns if the states never repeat.
+pub fn brent<S: Clone, K: PartialEq>(
+    initial: &S,
+    step: impl FnMut(&mut S),
+    key: impl FnMut(&S) -> K,
+) -> Cycle {
+    brent_within(initial, usize::MAX, step, key)
+        .unwrap_or_else(|_| unreachable!("Ran out of steps before the states repeated"))
+}
+
+/// `state_after` using Brent's algorithm, which steps from the start again
+/// once it knows the cycle
+pub fn state_after_brent<S: Clone, K: PartialEq>(
+    initial: S,
+    n: usize,
+    mut step: impl FnMut(&mut S),
+    key: impl FnMut(&S) -> K,
+) -> (S, Option<Cycle>) {
+    match brent_within(&initial, n, &mut step, key) {
+        Ok(cycle) => {
+            let mut state = initial;
+            for _ in 0..cycle.equivalent(n) {
+                step(&mut state);
+            }
+            (state, Some(cycle))
+        }
+        Err(state) => (state, None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 0, 1, 2, then 3, 4, 5 over and over
+    fn next(n: &mut u32) {
+        *n = match *n {
+            5 => 3,
+            n => n + 1,
+        }
+    }
+
+    #[test]
+    fn test_find() {
+        let expected = Cycle {
+            prefix: 3,
+            period: 3,
+        };
+
+        assert_eq!(find(&0, next, |&n| n), expected);
+        assert_eq!(brent(&0, next, |&n| n), expected);
+        assert_eq!(
+            find(&4, next, |&n| n),
+            Cycle {
+                prefix: 0,
+                period: 3
+            }
+        );
+        assert_eq!(
+            brent(&4, next, |&n| n),
+            Cycle {
+                prefix: 0,
+                period: 3
+            }
+        );
+    }
+
+    #[test]
+    fn test_fixed_point() {
+        let stuck = |_: &mut u32| {};
+
+        assert_eq!(
+            find(&7, stuck, |&n| n),
+            Cycle {
+                prefix: 0,
+                period: 1
+            }
+        );
+        assert_eq!(
+            brent(&7, stuck, |&n| n),
+            Cycle {
+                prefix: 0,
+                period: 1
+            }
+        );
+    }
+
+    #[test]
+    fn test_equivalent() {
+        let cycle = Cycle {
+            prefix: 3,
+            period: 3,
+        };
+
+        assert_eq!(cycle.equivalent(2), 2);
+        assert_eq!(cycle.equivalent(3), 3);
+        assert_eq!(cycle.equivalent(10), 4);
+        assert_eq!(cycle.laps(2), 0);
+        assert_eq!(cycle.laps(10), 2);
+    }
+
+    #[test]
+    fn test_state_after() {
+        for n in [0, 2, 3, 7, 1_000_000_000_000] {
+            let mut expected = 0;
+            let steps = Cycle {
+                prefix: 3,
+                period: 3,
+            }
+            .equivalent(n);
+            for _ in 0..steps {
+                next(&mut expected);
+            }
+
+            assert_eq!(state_after(0, n, next, |&n| n).0, expected, "{n}");
+            assert_eq!(state_after_brent(0, n, next, |&n| n).0, expected, "{n}");
+        }
+
+        assert_eq!(state_after(0, 2, next, |&n| n), (2, None));
+        assert_eq!(state_after_brent(0, 2, next, |&n| n), (2, None));
+    }
+
+    #[test]
+    fn test_key_leaves_out_count() {
+        // The count keeps going up but the position goes round a loop of 4
+        let step = |(count, position): &mut (usize, usize)| {
+            *count += 1;
+            *position = (*position + 1) % 4;
+        };
+
+        let (state, cycle) = state_after((0, 0), 10, step, |&(_, position)| position);
+        assert_eq!(state.1, 2);
+        assert_eq!(
+            cycle,
+            Some(Cycle {
+                prefix: 0,
+                period: 4
+            })
+        );
+    }
+}
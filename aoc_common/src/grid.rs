@@ -0,0 +1,454 @@
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+use crate::point::{Direction4, Direction8, Point2};
+
+/// An `(x, y)` position, `x` going right and `y` going down from the top left.
+/// Anything taking a position takes a `Point2` too.
+pub type Position = (isize, isize);
+
+#[derive(Debug, PartialEq)]
+pub enum GridError {
+    /// There were no rows to make a grid from
+    Empty,
+    /// A row was not as wide as the first one, `line` counts from 1
+    RaggedRow {
+        line: usize,
+        expected: usize,
+        found: usize,
+    },
+}
+
+impl fmt::Display for GridError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GridError::Empty => write!(f, "the grid has no rows"),
+            GridError::RaggedRow {
+                line,
+                expected,
+                found,
+            } => write!(f, "line {line} is {found} wide, expected {expected}"),
+        }
+    }
+}
+
+impl std::error::Error for GridError {}
+
+/// A rectangular grid stored row by row, the map most puzzles come with
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self {
+        Grid {
+            cells: vec![fill; width * height],
+            width,
+            height,
+        }
+    }
+}
+
+impl Grid<char> {
+    /// Reads a grid of characters, one line per row
+    pub fn parse(lines: &[impl AsRef<str>]) -> Result<Self, GridError> {
+        Self::parse_with(lines, |c| c)
+    }
+}
+
+impl<T> Grid<T> {
+    /// Reads one line per row, turning each character into a cell
+    pub fn parse_with(
+        lines: &[impl AsRef<str>],
+        mut cell: impl FnMut(char) -> T,
+    ) -> Result<Self, GridError> {
+        Self::from_rows(
+            lines
+                .iter()
+                .map(|l| l.as_ref().chars().map(&mut cell).collect::<Vec<_>>()),
+        )
+    }
+
+    /// Builds a grid from its rows, which all have to be the same width
+    pub fn from_rows<R>(rows: impl IntoIterator<Item = R>) -> Result<Self, GridError>
+    where
+        R: IntoIterator<Item = T>,
+    {
+        let mut cells = Vec::new();
+        let mut width = None;
+        let mut height = 0;
+
+        for row in rows {
+            let start = cells.len();
+            cells.extend(row);
+            let found = cells.len() - start;
+
+            match width {
+                None => width = Some(found),
+                Some(expected) if expected != found => {
+                    return Err(GridError::RaggedRow {
+                        line: height + 1,
+                        expected,
+                        found,
+                    })
+                }
+                Some(_) => {}
+            }
+            height += 1;
+        }
+
+        Ok(Grid {
+            cells,
+            width: width.ok_or(GridError::Empty)?,
+            height,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn in_bounds(&self, pos: impl Into<Position>) -> bool {
+        let (x, y) = pos.into();
+        x >= 0 && y >= 0 && (x as usize) < self.width && (y as usize) < self.height
+    }
+
+    fn index_of(&self, (x, y): Position) -> Option<usize> {
+        self.in_bounds((x, y))
+            .then(|| x as usize + y as usize * self.width)
+    }
+
+    fn position_of(&self, index: usize) -> Position {
+        ((index % self.width) as isize, (index / self.width) as isize)
+    }
+
+    pub fn get(&self, pos: impl Into<Position>) -> Option<&T> {
+        self.index_of(pos.into()).map(|ix| &self.cells[ix])
+    }
+
+    pub fn get_mut(&mut self, pos: impl Into<Position>) -> Option<&mut T> {
+        self.index_of(pos.into()).map(|ix| &mut self.cells[ix])
+    }
+
+    /// Replaces the cell, returning what was there. Nothing happens off the grid.
+    pub fn set(&mut self, pos: impl Into<Position>, value: T) -> Option<T> {
+        self.get_mut(pos).map(|cell| std::mem::replace(cell, value))
+    }
+
+    /// Brings a position back onto the grid as if the edges were joined up,
+    /// `None` when the grid has no cells to wrap onto
+    pub fn wrap(&self, pos: impl Into<Position>) -> Option<Position> {
+        if self.width == 0 || self.height == 0 {
+            return None;
+        }
+
+        let (x, y) = pos.into();
+        Some((
+            x.rem_euclid(self.width as isize),
+            y.rem_euclid(self.height as isize),
+        ))
+    }
+
+    /// The cell at the position after wrapping it, for maps that repeat forever
+    pub fn get_wrapping(&self, pos: impl Into<Position>) -> Option<&T> {
+        self.wrap(pos).map(|pos| &self[pos])
+    }
+
+    /// Every cell along with its position, row by row
+    pub fn iter(&self) -> impl Iterator<Item = (Position, &T)> {
+        self.cells
+            .iter()
+            .enumerate()
+            .map(|(ix, cell)| (self.position_of(ix), cell))
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = Position> + '_ {
+        (0..self.cells.len()).map(|ix| self.position_of(ix))
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn row(&self, y: usize) -> Option<&[T]> {
+        self.rows().nth(y)
+    }
+
+    /// The cells of a column from top to bottom, empty when it is off the grid
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        let height = if x < self.width { self.height } else { 0 };
+        (0..height).map(move |y| &self.cells[x + y * self.width])
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// Walks from the position in steps of `step` until it leaves the grid,
+    /// starting with the position itself
+    pub fn ray(
+        &self,
+        start: impl Into<Position>,
+        step: impl Into<Position>,
+    ) -> impl Iterator<Item = (Position, &T)> {
+        let step = step.into();
+        std::iter::successors(Some(start.into()), move |&(x, y)| {
+            Some((x + step.0, y + step.1))
+        })
+        .map_while(|pos| self.get(pos).map(|cell| (pos, cell)))
+    }
+
+    /// Every diagonal going down and to the right, starting from the top right
+    pub fn diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        let top = (0..self.width).rev().map(|x| (x as isize, 0));
+        let left = (1..self.height).map(|y| (0, y as isize));
+
+        top.chain(left)
+            .map(|start| self.ray(start, (1, 1)).map(|(_, cell)| cell))
+    }
+
+    /// Every diagonal going down and to the left, starting from the top left
+    pub fn anti_diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        let right_edge = self.width as isize - 1;
+        let top = (0..self.width).map(|x| (x as isize, 0));
+        let right = (1..self.height).map(move |y| (right_edge, y as isize));
+
+        top.chain(right)
+            .map(|start| self.ray(start, (-1, 1)).map(|(_, cell)| cell))
+    }
+
+    /// The neighbours above, right, below and left that are on the grid
+    pub fn neighbours4(&self, pos: impl Into<Position>) -> impl Iterator<Item = (Position, &T)> {
+        self.neighbours(pos.into(), Direction4::ALL.map(Direction4::offset))
+    }
+
+    /// All eight surrounding neighbours that are on the grid
+    pub fn neighbours8(&self, pos: impl Into<Position>) -> impl Iterator<Item = (Position, &T)> {
+        self.neighbours(pos.into(), Direction8::ALL.map(Direction8::offset))
+    }
+
+    fn neighbours<const N: usize>(
+        &self,
+        (x, y): Position,
+        offsets: [Point2; N],
+    ) -> impl Iterator<Item = (Position, &T)> {
+        offsets.into_iter().filter_map(move |offset| {
+            let pos = (x + offset.x, y + offset.y);
+            self.get(pos).map(|cell| (pos, cell))
+        })
+    }
+
+    /// The first matching position, reading row by row
+    pub fn find(&self, matches: impl FnMut(&T) -> bool) -> Option<Position> {
+        self.cells
+            .iter()
+            .position(matches)
+            .map(|ix| self.position_of(ix))
+    }
+
+    pub fn find_all<'a>(
+        &'a self,
+        mut matches: impl FnMut(&T) -> bool + 'a,
+    ) -> impl Iterator<Item = Position> + 'a {
+        self.iter()
+            .filter(move |(_, cell)| matches(cell))
+            .map(|(pos, _)| pos)
+    }
+
+    /// A grid the same size with every cell converted
+    pub fn map<U>(&self, convert: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            cells: self.cells.iter().map(convert).collect(),
+            width: self.width,
+            height: self.height,
+        }
+    }
+
+    /// Draws the grid one character per cell, with a newline after every row
+    pub fn render(&self, mut draw: impl FnMut(&T) -> char) -> String {
+        let mut out = String::with_capacity((self.width + 1) * self.height);
+
+        for row in self.rows() {
+            out.extend(row.iter().map(&mut draw));
+            out.push('\n');
+        }
+
+        out
+    }
+}
+
+impl<T> Index<Point2> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Point2) -> &T {
+        &self[(pos.x, pos.y)]
+    }
+}
+
+impl<T> IndexMut<Point2> for Grid<T> {
+    fn index_mut(&mut self, pos: Point2) -> &mut T {
+        &mut self[(pos.x, pos.y)]
+    }
+}
+
+impl<T> Index<Position> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Position) -> &T {
+        self.get(pos)
+            .unwrap_or_else(|| panic!("{pos:?} is off the {}x{} grid", self.width, self.height))
+    }
+}
+
+impl<T> IndexMut<Position> for Grid<T> {
+    fn index_mut(&mut self, pos: Position) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        self.get_mut(pos)
+            .unwrap_or_else(|| panic!("{pos:?} is off the {width}x{height} grid"))
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(text: &str) -> Vec<String> {
+        text.lines().map(String::from).collect()
+    }
+
+    fn grid() -> Grid<char> {
+        Grid::parse(&lines("abc\ndef")).unwrap()
+    }
+
+    #[test]
+    fn test_parse() {
+        let grid = grid();
+
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid.get((2, 1)), Some(&'f'));
+        assert_eq!(grid.get((3, 0)), None);
+        assert_eq!(grid.get((0, -1)), None);
+        assert_eq!(
+            Grid::parse(&lines("ab\nc")),
+            Err(GridError::RaggedRow {
+                line: 2,
+                expected: 2,
+                found: 1
+            })
+        );
+        assert_eq!(Grid::parse(&[] as &[&str]), Err(GridError::Empty));
+
+        let digits = Grid::parse_with(&lines("12\n34"), |c| c.to_digit(10).unwrap()).unwrap();
+        assert_eq!(digits[(1, 1)], 4);
+    }
+
+    #[test]
+    fn test_set_and_wrap() {
+        let mut grid = grid();
+
+        assert_eq!(grid.set((1, 0), 'x'), Some('b'));
+        assert_eq!(grid.set((5, 5), 'x'), None);
+        assert_eq!(grid.get_wrapping((4, -1)), Some(&'e'));
+        assert_eq!(grid.get_wrapping((-2, 2)), Some(&'x'));
+        assert_eq!(Grid::new(0, 3, '.').get_wrapping((1, 1)), None);
+        assert_eq!(grid[Point2::new(2, 1)], 'f');
+    }
+
+    #[test]
+    fn test_rows_and_columns() {
+        let grid = grid();
+
+        assert_eq!(
+            grid.rows().collect::<Vec<_>>(),
+            vec![&['a', 'b', 'c'], &['d', 'e', 'f']]
+        );
+        assert_eq!(grid.column(1).collect::<String>(), "be");
+        assert_eq!(grid.column(3).count(), 0);
+        assert_eq!(
+            grid.columns()
+                .map(|c| c.collect::<String>())
+                .collect::<Vec<_>>(),
+            vec!["ad", "be", "cf"]
+        );
+    }
+
+    #[test]
+    fn test_diagonals() {
+        let grid = grid();
+
+        assert_eq!(
+            grid.diagonals()
+                .map(|d| d.collect::<String>())
+                .collect::<Vec<_>>(),
+            vec!["c", "bf", "ae", "d"]
+        );
+        assert_eq!(
+            grid.anti_diagonals()
+                .map(|d| d.collect::<String>())
+                .collect::<Vec<_>>(),
+            vec!["a", "bd", "ce", "f"]
+        );
+        assert_eq!(
+            grid.ray((2, 1), (-1, 0))
+                .map(|(_, c)| *c)
+                .collect::<String>(),
+            "fed"
+        );
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let grid = grid();
+
+        assert_eq!(
+            grid.neighbours4((0, 0)).collect::<Vec<_>>(),
+            vec![((1, 0), &'b'), ((0, 1), &'d')]
+        );
+        assert_eq!(grid.neighbours8((1, 0)).count(), 5);
+        assert_eq!(grid.neighbours8((1, 1)).count(), 5);
+    }
+
+    #[test]
+    fn test_find() {
+        let grid = Grid::parse(&lines("#.#\n..#")).unwrap();
+
+        assert_eq!(grid.find(|&c| c == '.'), Some((1, 0)));
+        assert_eq!(grid.find(|&c| c == 'x'), None);
+        assert_eq!(
+            grid.find_all(|&c| c == '#').collect::<Vec<_>>(),
+            vec![(0, 0), (2, 0), (2, 1)]
+        );
+    }
+
+    #[test]
+    fn test_render() {
+        let grid = Grid::parse(&lines("#.\n.#")).unwrap();
+
+        assert_eq!(grid.to_string(), "#.\n.#\n");
+        assert_eq!(
+            grid.map(|&c| c == '#')
+                .render(|&wall| if wall { '█' } else { ' ' }),
+            "█ \n █\n"
+        );
+    }
+}
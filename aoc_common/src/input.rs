@@ -0,0 +1,257 @@
+//! Reading a day's input, so `main` does not start with the same stdin lines
+//! every time and a bad line says where it is instead of panicking.
+
+use std::fmt;
+use std::fs;
+use std::io::{self, prelude::*};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+#[derive(Debug)]
+pub enum InputError {
+    /// The file, or stdin when there is no path, could not be read
+    Read {
+        path: Option<PathBuf>,
+        source: io::Error,
+    },
+    /// A line did not hold what was expected, `line` counts from 1
+    Line { line: usize, message: String },
+}
+
+impl InputError {
+    /// A problem with a line, for parsers that check more than numbers
+    pub fn at(line: usize, message: impl fmt::Display) -> Self {
+        InputError::Line {
+            line,
+            message: message.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            InputError::Read {
+                path: Some(path),
+                source,
+            } => write!(f, "unable to read {}: {source}", path.display()),
+            InputError::Read { path: None, source } => {
+                write!(f, "unable to read stdin: {source}")
+            }
+            InputError::Line { line, message } => write!(f, "line {line}: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for InputError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            InputError::Read { source, .. } => Some(source),
+            InputError::Line { .. } => None,
+        }
+    }
+}
+
+/// Reads the input from the file, or from stdin without one, normalised
+pub fn read(path: Option<&Path>) -> Result<String, InputError> {
+    let text = match path {
+        Some(path) => fs::read_to_string(path),
+        None => {
+            let mut text = String::new();
+            io::stdin().read_to_string(&mut text).map(|_| text)
+        }
+    };
+
+    text.map(|text| normalise(&text))
+        .map_err(|source| InputError::Read {
+            path: path.map(Path::to_path_buf),
+            source,
+        })
+}
+
+/// Turns CRLF line endings into LF and drops the newlines at the end, so a
+/// file saved on Windows or without a final newline reads the same
+pub fn normalise(text: &str) -> String {
+    text.replace("\r\n", "\n")
+        .trim_end_matches('\n')
+        .to_string()
+}
+
+/// Lines separated from the next ones by a blank line, like one elf's snacks
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Block<'a> {
+    /// The line number of the block's first line, counting from 1
+    pub line: usize,
+    pub lines: Vec<&'a str>,
+}
+
+impl Block<'_> {
+    /// Parses each line of the block, errors give the line in the whole input
+    pub fn parse_lines<T, E: fmt::Display>(
+        &self,
+        parse: impl FnMut(&str) -> Result<T, E>,
+    ) -> Result<Vec<T>, InputError> {
+        parse_numbered(self.line, self.lines.iter().copied(), parse)
+    }
+}
+
+/// Splits the text at blank lines, any number of them in a row
+pub fn blocks(text: &str) -> Vec<Block<'_>> {
+    let mut blocks = Vec::new();
+    let mut current: Option<Block> = None;
+
+    for (ix, line) in text.lines().enumerate() {
+        match (line.trim().is_empty(), current.as_mut()) {
+            (true, _) => blocks.extend(current.take()),
+            (false, Some(block)) => block.lines.push(line),
+            (false, None) => {
+                current = Some(Block {
+                    line: ix + 1,
+                    lines: vec![line],
+                })
+            }
+        }
+    }
+    blocks.extend(current);
+
+    blocks
+}
+
+/// Every integer in the line in order, whatever is around them. A `-` is a
+/// minus sign unless it follows a digit, so `x=-3` is `-3` but `2-4` is a
+/// range.
+pub fn ints<T: FromStr>(line: &str) -> Result<Vec<T>, T::Err> {
+    let bytes = line.as_bytes();
+    let mut numbers = Vec::new();
+    let mut ix = 0;
+
+    while ix < bytes.len() {
+        let negative = bytes[ix] == b'-'
+            && bytes.get(ix + 1).is_some_and(u8::is_ascii_digit)
+            && !(ix > 0 && bytes[ix - 1].is_ascii_digit());
+        if !negative && !bytes[ix].is_ascii_digit() {
+            ix += 1;
+            continue;
+        }
+
+        let start = ix;
+        ix += 1;
+        while bytes.get(ix).is_some_and(u8::is_ascii_digit) {
+            ix += 1;
+        }
+        numbers.push(line[start..ix].parse()?);
+    }
+
+    Ok(numbers)
+}
+
+/// Parses each line, errors give the line they came from
+pub fn parse_lines<T, E: fmt::Display>(
+    text: &str,
+    parse: impl FnMut(&str) -> Result<T, E>,
+) -> Result<Vec<T>, InputError> {
+    parse_numbered(1, text.lines(), parse)
+}
+
+/// The integers on each line
+pub fn ints_by_line<T>(text: &str) -> Result<Vec<Vec<T>>, InputError>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    parse_lines(text, ints)
+}
+
+fn parse_numbered<'a, T, E: fmt::Display>(
+    first: usize,
+    lines: impl Iterator<Item = &'a str>,
+    mut parse: impl FnMut(&str) -> Result<T, E>,
+) -> Result<Vec<T>, InputError> {
+    lines
+        .enumerate()
+        .map(|(ix, line)| parse(line).map_err(|e| InputError::at(first + ix, e)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalise() {
+        assert_eq!(normalise("a\r\nb\r\n\r\n"), "a\nb");
+        assert_eq!(normalise("a\nb"), "a\nb");
+        assert_eq!(normalise(" a \n\n\n"), " a ");
+    }
+
+    #[test]
+    fn test_read_file() -> Result<(), Box<dyn std::error::Error>> {
+        // Named after the process so test runs at the same time do not share it
+        let name = format!("aoc_common_test_read_file_{}.txt", std::process::id());
+        let path = std::env::temp_dir().join(name);
+        fs::write(&path, "1\r\n2\r\n")?;
+        assert_eq!(read(Some(&path))?, "1\n2");
+        fs::remove_file(&path)?;
+
+        let err = read(Some(&path)).unwrap_err();
+        assert!(err.to_string().starts_with("unable to read "));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_blocks() {
+        let text = "1000\n2000\n\n4000\n\n\n5000\n6000";
+
+        assert_eq!(
+            blocks(text),
+            [
+                Block {
+                    line: 1,
+                    lines: vec!["1000", "2000"]
+                },
+                Block {
+                    line: 4,
+                    lines: vec!["4000"]
+                },
+                Block {
+                    line: 7,
+                    lines: vec!["5000", "6000"]
+                },
+            ]
+        );
+        assert_eq!(blocks(""), []);
+    }
+
+    #[test]
+    fn test_ints() {
+        assert_eq!(
+            ints::<i64>("Sensor at x=-2, y=15: beacon at x=10, y=-16"),
+            Ok(vec![-2, 15, 10, -16])
+        );
+        assert_eq!(ints::<u32>("2-4,6-8"), Ok(vec![2, 4, 6, 8]));
+        assert_eq!(ints::<i32>("move 1 - 3 --5"), Ok(vec![1, 3, -5]));
+        assert_eq!(ints::<i32>("no numbers -"), Ok(vec![]));
+        assert!(ints::<u8>("300").is_err());
+        assert!(ints::<u8>("-3").is_err());
+    }
+
+    #[test]
+    fn test_errors_have_line_numbers() {
+        let err = ints_by_line::<u8>("1 2\n3 400\n").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 2: number too large to fit in target type"
+        );
+
+        let text = "1\n\n2\nx";
+        let block = &blocks(text)[1];
+        let err = block
+            .parse_lines(|l| {
+                l.parse::<u8>()
+                    .map_err(|_| format!("{l:?} is not a number"))
+            })
+            .unwrap_err();
+        assert_eq!(err.to_string(), "line 4: \"x\" is not a number");
+    }
+}
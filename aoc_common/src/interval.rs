@@ -0,0 +1,351 @@
+//! Sets of numbers kept as sorted ranges, for puzzles that split ranges up or
+//! ask what a row of sensors leaves uncovered, where there are far too many
+//! numbers to hold one by one.
+
+use std::ops::{Add, Range, Sub};
+
+/// Numbers held as half open ranges, kept sorted, apart and merged wherever
+/// they touch. A puzzle's inclusive `a-b` is `a..b + 1`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IntervalSet<T> {
+    ranges: Vec<Range<T>>,
+}
+
+impl<T> Default for IntervalSet<T> {
+    fn default() -> Self {
+        IntervalSet { ranges: Vec::new() }
+    }
+}
+
+impl<T> IntervalSet<T>
+where
+    T: Copy + Ord + Default + Add<Output = T> + Sub<Output = T>,
+{
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the range, merging it with any it overlaps or touches
+    pub fn insert(&mut self, range: Range<T>) {
+        if range.is_empty() {
+            return;
+        }
+
+        let first = self.ranges.partition_point(|r| r.end < range.start);
+        let last = self.ranges.partition_point(|r| r.start <= range.end);
+        let merged = match first == last {
+            true => range,
+            false => {
+                self.ranges[first].start.min(range.start)..self.ranges[last - 1].end.max(range.end)
+            }
+        };
+
+        self.ranges.splice(first..last, [merged]);
+    }
+
+    /// Takes the range out, splitting any range it lands in the middle of
+    pub fn remove(&mut self, range: Range<T>) {
+        if range.is_empty() {
+            return;
+        }
+
+        let first = self.ranges.partition_point(|r| r.end <= range.start);
+        let last = self.ranges.partition_point(|r| r.start < range.end);
+        if first == last {
+            return;
+        }
+
+        let left = self.ranges[first].start..range.start;
+        let right = range.end..self.ranges[last - 1].end;
+        let kept = [left, right].into_iter().filter(|r| !r.is_empty());
+
+        self.ranges.splice(first..last, kept);
+    }
+
+    /// The ranges in order, none of them empty or touching another
+    pub fn ranges(&self) -> impl Iterator<Item = Range<T>> + '_ {
+        self.ranges.iter().cloned()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        let ix = self.ranges.partition_point(|r| r.end <= value);
+        self.ranges.get(ix).is_some_and(|r| r.start <= value)
+    }
+
+    /// Whether the whole range is in the set
+    pub fn contains_range(&self, range: &Range<T>) -> bool {
+        if range.is_empty() {
+            return true;
+        }
+        let ix = self.ranges.partition_point(|r| r.end <= range.start);
+        self.ranges
+            .get(ix)
+            .is_some_and(|r| r.start <= range.start && range.end <= r.end)
+    }
+
+    /// Whether any of the range is in the set
+    pub fn overlaps(&self, range: &Range<T>) -> bool {
+        let ix = self.ranges.partition_point(|r| r.end <= range.start);
+        !range.is_empty() && self.ranges.get(ix).is_some_and(|r| r.start < range.end)
+    }
+
+    /// How many numbers the set covers
+    pub fn total_length(&self) -> T {
+        self.ranges
+            .iter()
+            .fold(T::default(), |total, r| total + (r.end - r.start))
+    }
+
+    /// The smallest and one past the largest number in the set
+    pub fn span(&self) -> Option<Range<T>> {
+        let first = self.ranges.first()?;
+        let last = self.ranges.last()?;
+        Some(first.start..last.end)
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        let mut union = self.clone();
+        for range in other.ranges() {
+            union.insert(range);
+        }
+        union
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut ranges = Vec::new();
+        let (mut a, mut b) = (
+            self.ranges.iter().peekable(),
+            other.ranges.iter().peekable(),
+        );
+
+        while let (Some(x), Some(y)) = (a.peek(), b.peek()) {
+            let overlap = x.start.max(y.start)..x.end.min(y.end);
+            if !overlap.is_empty() {
+                ranges.push(overlap);
+            }
+            // Whichever ends first can not overlap anything further on
+            match x.end <= y.end {
+                true => a.next(),
+                false => b.next(),
+            };
+        }
+
+        IntervalSet { ranges }
+    }
+
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut difference = self.clone();
+        for range in other.ranges() {
+            difference.remove(range);
+        }
+        difference
+    }
+
+    /// The parts of `within` the set leaves out
+    pub fn gaps(&self, within: Range<T>) -> Self {
+        IntervalSet::from(within).difference(self)
+    }
+
+    /// The first number in `within` that is not in the set
+    pub fn first_gap(&self, within: Range<T>) -> Option<T> {
+        self.gaps(within).ranges.first().map(|r| r.start)
+    }
+
+    /// Sends each part of the set through the first rule whose source range
+    /// holds it, to the same place in that rule's destination, like an
+    /// almanac's seed-to-soil map. Anything no rule holds stays where it is.
+    pub fn map_through(&self, rules: impl IntoIterator<Item = Shift<T>>) -> Self {
+        let mut unmoved = self.clone();
+        let mut moved = IntervalSet::new();
+
+        for rule in rules {
+            let source = IntervalSet::from(rule.from.clone());
+            for part in unmoved.intersection(&source).ranges() {
+                moved.insert(rule.apply(part.start)..rule.apply(part.end));
+            }
+            unmoved = unmoved.difference(&source);
+        }
+
+        moved.union(&unmoved)
+    }
+}
+
+/// Moves the numbers in `from` so that `from.start` lands on `to`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Shift<T> {
+    pub from: Range<T>,
+    pub to: T,
+}
+
+impl<T: Copy + Add<Output = T> + Sub<Output = T>> Shift<T> {
+    /// Where a number in, or at the end of, `from` ends up
+    pub fn apply(&self, value: T) -> T {
+        self.to + (value - self.from.start)
+    }
+}
+
+impl<T> From<Range<T>> for IntervalSet<T>
+where
+    T: Copy + Ord + Default + Add<Output = T> + Sub<Output = T>,
+{
+    fn from(range: Range<T>) -> Self {
+        let mut set = IntervalSet::new();
+        set.insert(range);
+        set
+    }
+}
+
+impl<T> FromIterator<Range<T>> for IntervalSet<T>
+where
+    T: Copy + Ord + Default + Add<Output = T> + Sub<Output = T>,
+{
+    fn from_iter<I: IntoIterator<Item = Range<T>>>(iter: I) -> Self {
+        let mut set = IntervalSet::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl<T> Extend<Range<T>> for IntervalSet<T>
+where
+    T: Copy + Ord + Default + Add<Output = T> + Sub<Output = T>,
+{
+    fn extend<I: IntoIterator<Item = Range<T>>>(&mut self, iter: I) {
+        for range in iter {
+            self.insert(range);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set(ranges: &[Range<i32>]) -> IntervalSet<i32> {
+        ranges.iter().cloned().collect()
+    }
+
+    fn ranges(set: &IntervalSet<i32>) -> Vec<Range<i32>> {
+        set.ranges().collect()
+    }
+
+    #[test]
+    fn test_insert_merges() {
+        let mut s = set(&[0..2, 5..7, 10..12]);
+        assert_eq!(ranges(&s), [0..2, 5..7, 10..12]);
+
+        s.insert(2..3);
+        assert_eq!(ranges(&s), [0..3, 5..7, 10..12]);
+
+        s.insert(6..11);
+        assert_eq!(ranges(&s), [0..3, 5..12]);
+
+        s.insert(-5..20);
+        assert_eq!(s, IntervalSet::from(-5..20));
+
+        s.insert(4..4);
+        assert_eq!(s, IntervalSet::from(-5..20));
+    }
+
+    #[test]
+    fn test_remove_splits() {
+        let mut s = set(&[0..10, 20..30]);
+
+        s.remove(3..5);
+        assert_eq!(ranges(&s), [0..3, 5..10, 20..30]);
+
+        s.remove(8..25);
+        assert_eq!(ranges(&s), [0..3, 5..8, 25..30]);
+
+        s.remove(40..50);
+        s.remove(0..3);
+        assert_eq!(ranges(&s), [5..8, 25..30]);
+    }
+
+    #[test]
+    fn test_lookups() {
+        let s = set(&[0..3, 5..8]);
+
+        assert!(s.contains(0));
+        assert!(!s.contains(3));
+        assert!(s.contains(7));
+        assert!(s.contains_range(&(5..8)));
+        assert!(!s.contains_range(&(2..6)));
+        assert!(s.overlaps(&(2..6)));
+        assert!(!s.overlaps(&(3..5)));
+        assert_eq!(s.span(), Some(0..8));
+        assert_eq!(IntervalSet::<i32>::new().span(), None);
+    }
+
+    #[test]
+    fn test_set_operations() {
+        let a = set(&[0..10, 20..30]);
+        let b = IntervalSet::from(5..25);
+
+        assert_eq!(a.union(&b), IntervalSet::from(0..30));
+        assert_eq!(ranges(&a.intersection(&b)), [5..10, 20..25]);
+        assert_eq!(ranges(&a.difference(&b)), [0..5, 25..30]);
+        assert_eq!(b.difference(&a), IntervalSet::from(10..20));
+        assert_eq!(a.total_length(), 20);
+    }
+
+    #[test]
+    fn test_gaps() {
+        // The sensors covering row 11 of the 2022 day 15 example
+        let row = set(&[-3..14, 15..26, 12..13, 11..14]);
+
+        assert_eq!(row.gaps(0..21), IntervalSet::from(14..15));
+        assert_eq!(row.first_gap(0..21), Some(14));
+        assert_eq!(row.first_gap(0..14), None);
+    }
+
+    #[test]
+    fn test_map_through() {
+        // seed-to-soil and soil-to-fertilizer from the 2023 day 5 example
+        let seeds: IntervalSet<u64> = [79..93, 55..68].into_iter().collect();
+        let soil = seeds.map_through([
+            Shift {
+                from: 98..100,
+                to: 50,
+            },
+            Shift {
+                from: 50..98,
+                to: 52,
+            },
+        ]);
+        assert_eq!(soil.ranges().collect::<Vec<_>>(), [57..70, 81..95]);
+
+        let fertilizer = soil.map_through([
+            Shift {
+                from: 15..52,
+                to: 0,
+            },
+            Shift {
+                from: 52..54,
+                to: 37,
+            },
+            Shift {
+                from: 0..15,
+                to: 39,
+            },
+        ]);
+        assert_eq!(fertilizer, soil);
+
+        // The first rule to hold a number moves it
+        let moved = IntervalSet::from(0..10).map_through([
+            Shift {
+                from: 0..5,
+                to: 100,
+            },
+            Shift {
+                from: 0..10,
+                to: 200,
+            },
+        ]);
+        assert_eq!(moved.ranges().collect::<Vec<_>>(), [100..105, 205..210]);
+    }
+}
@@ -0,0 +1,19 @@
+//! The pieces more than one day needs, shared by every year. Days depend on it
+//! by path, `aoc_common = { path = "../../aoc_common" }`.
+
+pub mod cycle;
+pub mod grid;
+pub mod input;
+pub mod interval;
+pub mod math;
+pub mod memo;
+pub mod parse;
+pub mod point;
+pub mod search;
+pub mod solution;
+pub mod sparse;
+
+pub use grid::Grid;
+pub use point::{Direction4, Direction8, Point2, Point3};
+pub use solution::{Answer, Solution};
+pub use sparse::SparseGrid;
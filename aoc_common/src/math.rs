@@ -0,0 +1,473 @@
+//! Number theory and exact linear algebra, for the days where the answer is
+//! when several loops line up or where a button press count has to come out
+//! whole.
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, Div, Mul, Neg, Rem, Sub};
+
+/// Any of the built in integer types from `u8` and `i16` up
+pub trait Integer:
+    Copy
+    + Ord
+    + Default
+    + From<u8>
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Rem<Output = Self>
+{
+}
+
+impl<T> Integer for T where
+    T: Copy
+        + Ord
+        + Default
+        + From<u8>
+        + Add<Output = T>
+        + Sub<Output = T>
+        + Mul<Output = T>
+        + Div<Output = T>
+        + Rem<Output = T>
+{
+}
+
+fn abs<T: Integer>(n: T) -> T {
+    match n < T::default() {
+        true => T::default() - n,
+        false => n,
+    }
+}
+
+/// Greatest common divisor, never negative, and `gcd(0, 0)` is 0
+pub fn gcd<T: Integer>(a: T, b: T) -> T {
+    let (mut a, mut b) = (abs(a), abs(b));
+    while b != T::default() {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// Least common multiple, never negative, and 0 if either is 0
+pub fn lcm<T: Integer>(a: T, b: T) -> T {
+    if a == T::default() || b == T::default() {
+        return T::default();
+    }
+    abs(a / gcd(a, b) * b)
+}
+
+/// The gcd of all the numbers, 0 when there are none
+pub fn gcd_all<T: Integer>(numbers: impl IntoIterator<Item = T>) -> T {
+    numbers.into_iter().fold(T::default(), gcd)
+}
+
+/// The lcm of all the numbers, like when a set of loops all line up again.
+/// 1 when there are none.
+pub fn lcm_all<T: Integer>(numbers: impl IntoIterator<Item = T>) -> T {
+    numbers.into_iter().fold(T::from(1), lcm)
+}
+
+/// `(g, x, y)` with `a * x + b * y == g`, where `g` is `gcd(a, b)`
+pub fn extended_gcd(a: i64, b: i64) -> (i64, i64, i64) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (1, 0);
+    let (mut old_y, mut y) = (0, 1);
+
+    while r != 0 {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_x, x) = (x, old_x - q * x);
+        (old_y, y) = (y, old_y - q * y);
+    }
+
+    match old_r < 0 {
+        true => (-old_r, -old_x, -old_y),
+        false => (old_r, old_x, old_y),
+    }
+}
+
+/// `a * b % m` without overflowing, between 0 and `m`
+pub fn mod_mul(a: i64, b: i64, m: i64) -> i64 {
+    (a as i128 * b as i128).rem_euclid(m as i128) as i64
+}
+
+/// `base` to the power `exp`, modulo `m`, by squaring
+pub fn mod_pow(base: i64, mut exp: u64, m: i64) -> i64 {
+    let mut base = base.rem_euclid(m);
+    let mut result = 1 % m;
+
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = mod_mul(result, base, m);
+        }
+        base = mod_mul(base, base, m);
+        exp >>= 1;
+    }
+
+    result
+}
+
+/// The `x` between 0 and `m` with `a * x` leaving 1 modulo `m`, when `a` and
+/// `m` have no common factor
+pub fn mod_inverse(a: i64, m: i64) -> Option<i64> {
+    let (g, x, _) = extended_gcd(a.rem_euclid(m), m);
+    (g == 1).then(|| x.rem_euclid(m))
+}
+
+/// The Chinese remainder theorem, for `(remainder, modulus)` pairs. Gives the
+/// smallest `x` that leaves every remainder, with the modulus it repeats
+/// over, which is the lcm of them all. The moduli do not need to be coprime,
+/// but `None` when their remainders can not all be met.
+pub fn crt(pairs: impl IntoIterator<Item = (i64, i64)>) -> Option<(i64, i64)> {
+    let mut x = 0;
+    let mut modulus = 1;
+
+    for (remainder, m) in pairs {
+        // Want x + modulus * k == remainder, modulo m
+        let (g, inverse, _) = extended_gcd(modulus, m);
+        let difference = remainder - x;
+        if difference % g != 0 {
+            return None;
+        }
+
+        let step = m / g;
+        let k = mod_mul(difference / g, inverse, step);
+        let combined = modulus as i128 * step as i128;
+        x = ((x as i128 + modulus as i128 * k as i128).rem_euclid(combined)) as i64;
+        modulus = combined as i64;
+    }
+
+    Some((x, modulus))
+}
+
+/// An exact fraction, always in its lowest terms with a positive denominator
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Fraction {
+    numerator: i128,
+    denominator: i128,
+}
+
+impl Fraction {
+    pub fn new(numerator: i128, denominator: i128) -> Self {
+        assert!(denominator != 0, "Fraction with a denominator of 0");
+        let g = gcd(numerator, denominator);
+        let sign = denominator.signum();
+        Fraction {
+            numerator: sign * numerator / g,
+            denominator: sign * denominator / g,
+        }
+    }
+
+    pub fn numerator(&self) -> i128 {
+        self.numerator
+    }
+
+    pub fn denominator(&self) -> i128 {
+        self.denominator
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.numerator == 0
+    }
+
+    /// The whole number, if it is one and fits in an `i64`
+    pub fn to_integer(&self) -> Option<i64> {
+        match self.denominator {
+            1 => i64::try_from(self.numerator).ok(),
+            _ => None,
+        }
+    }
+}
+
+impl From<i64> for Fraction {
+    fn from(n: i64) -> Self {
+        Fraction {
+            numerator: n as i128,
+            denominator: 1,
+        }
+    }
+}
+
+impl fmt::Display for Fraction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.denominator {
+            1 => write!(f, "{}", self.numerator),
+            d => write!(f, "{}/{d}", self.numerator),
+        }
+    }
+}
+
+impl Add for Fraction {
+    type Output = Fraction;
+
+    fn add(self, other: Fraction) -> Fraction {
+        Fraction::new(
+            self.numerator * other.denominator + other.numerator * self.denominator,
+            self.denominator * other.denominator,
+        )
+    }
+}
+
+impl Neg for Fraction {
+    type Output = Fraction;
+
+    fn neg(self) -> Fraction {
+        Fraction {
+            numerator: -self.numerator,
+            ..self
+        }
+    }
+}
+
+impl Sub for Fraction {
+    type Output = Fraction;
+
+    fn sub(self, other: Fraction) -> Fraction {
+        self + -other
+    }
+}
+
+impl Mul for Fraction {
+    type Output = Fraction;
+
+    fn mul(self, other: Fraction) -> Fraction {
+        Fraction::new(
+            self.numerator * other.numerator,
+            self.denominator * other.denominator,
+        )
+    }
+}
+
+impl Div for Fraction {
+    type Output = Fraction;
+
+    /// Panics dividing by zero, like the integers do
+    fn div(self, other: Fraction) -> Fraction {
+        Fraction::new(
+            self.numerator * other.denominator,
+            self.denominator * other.numerator,
+        )
+    }
+}
+
+impl PartialOrd for Fraction {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Fraction {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.numerator * other.denominator).cmp(&(other.numerator * self.denominator))
+    }
+}
+
+/// What a set of linear equations comes to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LinearSolution {
+    /// One value for each unknown
+    Unique(Vec<Fraction>),
+    /// The equations contradict each other
+    NoSolution,
+    /// Some unknowns can be anything, with the others following from them
+    Infinite,
+}
+
+impl LinearSolution {
+    /// The values when there is exactly one answer and it is all whole numbers,
+    /// like how many times to press each button
+    pub fn integers(&self) -> Option<Vec<i64>> {
+        match self {
+            LinearSolution::Unique(values) => values.iter().map(Fraction::to_integer).collect(),
+            _ => None,
+        }
+    }
+
+    /// The values when there is exactly one answer and it is all whole numbers
+    /// of zero or more, since a button can not be pressed a negative number
+    /// of times
+    pub fn naturals(&self) -> Option<Vec<u64>> {
+        self.integers()?
+            .into_iter()
+            .map(|value| u64::try_from(value).ok())
+            .collect()
+    }
+}
+
+/// Solves the equations exactly, each row of `coefficients` times the
+/// unknowns equalling the matching entry of `targets`. With two buttons
+/// that move a claw by `(94, 34)` and `(22, 67)` to reach `(8400, 5400)`,
+/// the rows are `[94, 22]` and `[34, 67]` and the targets `[8400, 5400]`.
+pub fn solve_linear(coefficients: &[Vec<i64>], targets: &[i64]) -> LinearSolution {
+    assert_eq!(
+        coefficients.len(),
+        targets.len(),
+        "Need one target for each equation"
+    );
+    let unknowns = coefficients.first().map_or(0, Vec::len);
+
+    // Each row is the coefficients with the target on the end
+    let mut rows: Vec<Vec<Fraction>> = coefficients
+        .iter()
+        .zip(targets)
+        .map(|(row, &target)| {
+            assert_eq!(row.len(), unknowns, "Every equation needs every unknown");
+            row.iter()
+                .chain([&target])
+                .map(|&n| Fraction::from(n))
+                .collect()
+        })
+        .collect();
+
+    // Gauss-Jordan elimination, leaving each pivot as 1 with 0s above and below
+    let mut pivots = Vec::new();
+    for column in 0..unknowns {
+        let row = pivots.len();
+        let Some(found) = (row..rows.len()).find(|&r| !rows[r][column].is_zero()) else {
+            continue;
+        };
+        rows.swap(row, found);
+
+        let pivot = rows[row][column];
+        for value in rows[row].iter_mut() {
+            *value = *value / pivot;
+        }
+
+        let pivot_row = rows[row].clone();
+        for (other, values) in rows.iter_mut().enumerate() {
+            let factor = values[column];
+            if other == row || factor.is_zero() {
+                continue;
+            }
+            for (value, &p) in values.iter_mut().zip(&pivot_row) {
+                *value = *value - factor * p;
+            }
+        }
+
+        pivots.push(column);
+    }
+
+    // A row of no unknowns that still has a target says 0 is something else
+    if rows[pivots.len()..]
+        .iter()
+        .any(|row| !row[unknowns].is_zero())
+    {
+        return LinearSolution::NoSolution;
+    }
+    if pivots.len() < unknowns {
+        return LinearSolution::Infinite;
+    }
+
+    LinearSolution::Unique(
+        rows.iter()
+            .take(unknowns)
+            .map(|row| row[unknowns])
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gcd_lcm() {
+        assert_eq!(gcd(12, 18), 6);
+        assert_eq!(gcd(-12, 18), 6);
+        assert_eq!(gcd(0, 5), 5);
+        assert_eq!(gcd(0u32, 0), 0);
+        assert_eq!(lcm(4, 6), 12);
+        assert_eq!(lcm(-4, 6), 12);
+        assert_eq!(lcm(0, 6), 0);
+
+        assert_eq!(gcd_all([12, 18, 27]), 3);
+        assert_eq!(lcm_all([2usize, 3, 4, 5]), 60);
+        assert_eq!(lcm_all(Vec::<u64>::new()), 1);
+        // The 2022 day 11 example monkeys' tests
+        assert_eq!(lcm_all([23u64, 19, 13, 17]), 96577);
+    }
+
+    #[test]
+    fn test_extended_gcd() {
+        for (a, b) in [(240, 46), (46, 240), (-240, 46), (7, 0), (0, 7)] {
+            let (g, x, y) = extended_gcd(a, b);
+            assert_eq!(g, gcd(a, b), "{a} {b}");
+            assert_eq!(a * x + b * y, g, "{a} {b}");
+        }
+    }
+
+    #[test]
+    fn test_modular() {
+        assert_eq!(mod_pow(4, 13, 497), 445);
+        assert_eq!(mod_pow(-2, 3, 5), 2);
+        assert_eq!(mod_pow(3, 0, 1), 0);
+        assert_eq!(mod_pow(2, 100, 1_000_000_007), 976371285);
+
+        assert_eq!(mod_inverse(3, 11), Some(4));
+        assert_eq!(mod_inverse(-3, 11), Some(7));
+        assert_eq!(mod_inverse(6, 9), None);
+    }
+
+    #[test]
+    fn test_crt() {
+        assert_eq!(crt([(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        assert_eq!(crt([(-1, 4), (3, 6)]), Some((3, 12)));
+        assert_eq!(crt([(1, 4), (2, 6)]), None);
+        assert_eq!(crt([]), Some((0, 1)));
+
+        // A robot back at x 3 every 101 seconds and y 10 every 103
+        let (t, period) = crt([(3, 101), (10, 103)]).unwrap();
+        assert_eq!((t % 101, t % 103, period), (3, 10, 10403));
+    }
+
+    #[test]
+    fn test_fraction() {
+        let half = Fraction::new(2, 4);
+        assert_eq!(half, Fraction::new(-1, -2));
+        assert_eq!(half.to_string(), "1/2");
+        assert_eq!((half + half).to_integer(), Some(1));
+        assert_eq!(Fraction::new(3, -6).to_string(), "-1/2");
+        assert_eq!(half * Fraction::from(3) - half, Fraction::from(1));
+        assert!(Fraction::new(1, 3) < half);
+        assert_eq!(half.to_integer(), None);
+    }
+
+    #[test]
+    fn test_solve_linear() {
+        // The first claw machine in the 2024 day 13 example
+        let solution = solve_linear(&[vec![94, 22], vec![34, 67]], &[8400, 5400]);
+        assert_eq!(solution.integers(), Some(vec![80, 40]));
+        assert_eq!(solution.naturals(), Some(vec![80, 40]));
+
+        // Reaching the prize needs pressing a button backwards
+        let solution = solve_linear(&[vec![1, 1], vec![0, 1]], &[2, 3]);
+        assert_eq!(solution.integers(), Some(vec![-1, 3]));
+        assert_eq!(solution.naturals(), None);
+
+        // The second has no whole number of presses
+        let solution = solve_linear(&[vec![26, 67], vec![66, 21]], &[12748, 12176]);
+        assert!(matches!(solution, LinearSolution::Unique(_)));
+        assert_eq!(solution.integers(), None);
+
+        assert_eq!(
+            solve_linear(&[vec![1, 2], vec![2, 4]], &[3, 7]),
+            LinearSolution::NoSolution
+        );
+        assert_eq!(
+            solve_linear(&[vec![1, 2], vec![2, 4]], &[3, 6]),
+            LinearSolution::Infinite
+        );
+        assert_eq!(
+            solve_linear(
+                &[vec![2, 1, -1], vec![-3, -1, 2], vec![-2, 1, 2]],
+                &[8, -11, -3]
+            ),
+            LinearSolution::Unique(vec![2.into(), 3.into(), Fraction::from(-1)])
+        );
+        assert_eq!(
+            solve_linear(&[vec![1], vec![2], vec![3]], &[2, 4, 6]).integers(),
+            Some(vec![2])
+        );
+    }
+}
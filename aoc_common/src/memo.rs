@@ -0,0 +1,224 @@
+//! Remembering the answers to recursive functions, with counts of how often
+//! the cache helped so it is easy to tell whether it is doing anything.
+
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::hash::Hash;
+
+/// A cache of values by key. A bounded one forgets its oldest entries first.
+#[derive(Debug, Clone)]
+pub struct Memo<K, V> {
+    values: HashMap<K, V>,
+    /// Keys oldest first, only kept when bounded
+    order: VecDeque<K>,
+    capacity: Option<usize>,
+    hits: usize,
+    misses: usize,
+}
+
+/// How well a `Memo` has been doing
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub hits: usize,
+    pub misses: usize,
+    pub entries: usize,
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let lookups = self.hits + self.misses;
+        let rate = match lookups {
+            0 => 0.0,
+            _ => 100.0 * self.hits as f64 / lookups as f64,
+        };
+        write!(
+            f,
+            "{} hits, {} misses ({rate:.1}% hit), {} entries",
+            self.hits, self.misses, self.entries
+        )
+    }
+}
+
+impl<K: Eq + Hash + Clone, V: Clone> Default for Memo<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Eq + Hash + Clone, V: Clone> Memo<K, V> {
+    pub fn new() -> Self {
+        Memo {
+            values: HashMap::new(),
+            order: VecDeque::new(),
+            capacity: None,
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    /// Holds at most `capacity` values
+    pub fn bounded(capacity: usize) -> Self {
+        Memo {
+            capacity: Some(capacity),
+            ..Self::new()
+        }
+    }
+
+    /// The remembered value for the key, or works it out with `f` and
+    /// remembers it. `f` gets the memo, so it can look up smaller keys.
+    pub fn get_or_insert_with(&mut self, key: K, f: impl FnOnce(&mut Self) -> V) -> V {
+        if let Some(value) = self.values.get(&key) {
+            self.hits += 1;
+            return value.clone();
+        }
+
+        self.misses += 1;
+        let value = f(self);
+        self.insert(key, value.clone());
+        value
+    }
+
+    /// Memoises a recursive function. `f` is given a function to call instead
+    /// of itself, and the key to work out, like `|fib, n| fib(n - 1) + fib(n - 2)`
+    pub fn recurse<F>(&mut self, key: K, f: &F) -> V
+    where
+        F: Fn(&mut dyn FnMut(K) -> V, K) -> V,
+    {
+        self.get_or_insert_with(key.clone(), |memo| {
+            f(&mut |smaller| memo.recurse(smaller, f), key)
+        })
+    }
+
+    fn insert(&mut self, key: K, value: V) {
+        let Some(capacity) = self.capacity else {
+            self.values.insert(key, value);
+            return;
+        };
+        if capacity == 0 {
+            return;
+        }
+
+        // A recursive call may have put the key in already
+        if self.values.insert(key.clone(), value).is_none() {
+            self.order.push_back(key);
+        }
+        while self.values.len() > capacity {
+            if let Some(oldest) = self.order.pop_front() {
+                self.values.remove(&oldest);
+            }
+        }
+    }
+
+    pub fn get(&self, key: &K) -> Option<&V> {
+        self.values.get(key)
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    pub fn stats(&self) -> Stats {
+        Stats {
+            hits: self.hits,
+            misses: self.misses,
+            entries: self.values.len(),
+        }
+    }
+
+    /// Forgets every value and starts the counts again, for a part that
+    /// needs different answers for the same keys
+    pub fn clear(&mut self) {
+        self.values.clear();
+        self.order.clear();
+        self.hits = 0;
+        self.misses = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fib(memo: &mut Memo<u64, u64>, n: u64) -> u64 {
+        memo.recurse(n, &|fib, n| match n {
+            0 | 1 => n,
+            n => fib(n - 1) + fib(n - 2),
+        })
+    }
+
+    #[test]
+    fn test_recurse() {
+        let mut memo = Memo::new();
+
+        assert_eq!(fib(&mut memo, 90), 2880067194370816120);
+        assert_eq!(
+            memo.stats(),
+            Stats {
+                hits: 88,
+                misses: 91,
+                entries: 91
+            }
+        );
+
+        assert_eq!(fib(&mut memo, 90), 2880067194370816120);
+        assert_eq!(memo.stats().hits, 89);
+    }
+
+    #[test]
+    fn test_get_or_insert_with() {
+        let mut memo = Memo::new();
+        let mut calls = 0;
+
+        for _ in 0..3 {
+            let value = memo.get_or_insert_with("seven", |_| {
+                calls += 1;
+                7
+            });
+            assert_eq!(value, 7);
+        }
+
+        assert_eq!(calls, 1);
+        assert_eq!(memo.get(&"seven"), Some(&7));
+        assert_eq!(
+            memo.stats().to_string(),
+            "2 hits, 1 misses (66.7% hit), 1 entries"
+        );
+    }
+
+    #[test]
+    fn test_bounded() {
+        let mut memo = Memo::bounded(2);
+        for n in [1, 2, 3] {
+            memo.get_or_insert_with(n, |_| n * 10);
+        }
+
+        assert_eq!(memo.len(), 2);
+        assert_eq!(memo.get(&1), None);
+        assert_eq!(memo.get(&3), Some(&30));
+
+        let mut memo = Memo::bounded(5);
+        assert_eq!(fib(&mut memo, 60), 1548008755920);
+        assert_eq!(memo.len(), 5);
+    }
+
+    #[test]
+    fn test_clear() {
+        let mut memo = Memo::new();
+        fib(&mut memo, 10);
+        memo.clear();
+
+        assert!(memo.is_empty());
+        assert_eq!(
+            memo.stats(),
+            Stats {
+                hits: 0,
+                misses: 0,
+                entries: 0
+            }
+        );
+    }
+}
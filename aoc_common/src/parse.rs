@@ -0,0 +1,288 @@
+//! nom parsers the puzzles keep needing, and one error that says where the
+//! input went wrong.
+
+use std::fmt;
+use std::str::FromStr;
+
+use nom::{
+    branch::alt,
+    character::complete::{char, digit1, line_ending, multispace0, none_of, space0, space1},
+    combinator::{all_consuming, map_opt, map_res, opt, recognize},
+    error::ErrorKind,
+    multi::{many1, separated_list1},
+    sequence::{pair, separated_pair, terminated},
+    Finish, IResult,
+};
+
+use crate::grid::Grid;
+
+/// Where the input stopped making sense, shown with a caret under the spot.
+/// `Debug` shows the same, so it reads well from `main` and `expect` too.
+#[derive(Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// Counting from 1
+    pub line: usize,
+    /// Counting characters from 1
+    pub column: usize,
+    /// The whole line that failed
+    pub text: String,
+    pub message: String,
+}
+
+impl ParseError {
+    /// Points at where `rest` starts within `input`
+    fn new(input: &str, rest: &str, kind: ErrorKind) -> Self {
+        let offset = input.len() - rest.len();
+        let before = &input[..offset];
+        let line_start = before.rfind('\n').map_or(0, |ix| ix + 1);
+        let text = input[line_start..].lines().next().unwrap_or_default();
+
+        ParseError {
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            text: text.to_string(),
+            message: describe(kind),
+        }
+    }
+
+    /// For an error from parsing one line on its own, which line of the whole
+    /// input that was
+    pub fn on_line(self, line: usize) -> Self {
+        ParseError {
+            line: line + self.line - 1,
+            ..self
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )?;
+        writeln!(f, "{}", self.text)?;
+        write!(f, "{:>1$}", "^", self.column)
+    }
+}
+
+impl fmt::Debug for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{self}")
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+fn describe(kind: ErrorKind) -> String {
+    match kind {
+        ErrorKind::Tag | ErrorKind::Char => "unexpected text".to_string(),
+        ErrorKind::Digit => "expected a number".to_string(),
+        ErrorKind::Alpha => "expected letters".to_string(),
+        ErrorKind::AlphaNumeric => "expected letters or digits".to_string(),
+        ErrorKind::Space | ErrorKind::MultiSpace => "expected a space".to_string(),
+        ErrorKind::CrLf => "expected the end of the line".to_string(),
+        ErrorKind::Eof => "expected the end of the input".to_string(),
+        ErrorKind::MapRes => "unable to convert this".to_string(),
+        ErrorKind::MapOpt | ErrorKind::NoneOf | ErrorKind::OneOf => {
+            "unexpected character".to_string()
+        }
+        kind => format!("expected {}", kind.description().to_lowercase()),
+    }
+}
+
+/// Runs the parser over all of the input, only whitespace can be left over
+pub fn parse_all<'a, T>(
+    input: &'a str,
+    parser: impl FnMut(&'a str) -> IResult<&'a str, T>,
+) -> Result<T, ParseError> {
+    all_consuming(terminated(parser, multispace0))(input)
+        .finish()
+        .map(|(_, parsed)| parsed)
+        .map_err(|e| ParseError::new(input, e.input, e.code))
+}
+
+/// Runs the parser over each line on its own
+pub fn parse_each_line<'a, T>(
+    text: &'a str,
+    mut parser: impl FnMut(&'a str) -> IResult<&'a str, T>,
+) -> Result<Vec<T>, ParseError> {
+    text.lines()
+        .enumerate()
+        .map(|(ix, line)| parse_all(line, &mut parser).map_err(|e| e.on_line(ix + 1)))
+        .collect()
+}
+
+/// Digits, as any number type
+pub fn unsigned<T: FromStr>(input: &str) -> IResult<&str, T> {
+    map_res(digit1, str::parse)(input)
+}
+
+/// Digits with an optional `-` or `+` in front
+pub fn signed<T: FromStr>(input: &str) -> IResult<&str, T> {
+    map_res(
+        recognize(pair(opt(alt((char('-'), char('+')))), digit1)),
+        str::parse,
+    )(input)
+}
+
+/// Items separated by commas, with or without spaces after them
+pub fn comma_list<'a, O>(
+    item: impl FnMut(&'a str) -> IResult<&'a str, O>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>> {
+    separated_list1(pair(char(','), space0), item)
+}
+
+/// Items separated by any number of spaces, like numbers lined up in columns
+pub fn space_list<'a, O>(
+    item: impl FnMut(&'a str) -> IResult<&'a str, O>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>> {
+    separated_list1(space1, item)
+}
+
+/// `key: value`, any spaces after the colon are skipped
+pub fn key_value<'a, K, V>(
+    key: impl FnMut(&'a str) -> IResult<&'a str, K>,
+    value: impl FnMut(&'a str) -> IResult<&'a str, V>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, (K, V)> {
+    separated_pair(key, pair(char(':'), space0), value)
+}
+
+/// One item per line
+pub fn lines<'a, O>(
+    item: impl FnMut(&'a str) -> IResult<&'a str, O>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>> {
+    separated_list1(line_ending, item)
+}
+
+/// Items separated by a blank line
+pub fn blocks<'a, O>(
+    item: impl FnMut(&'a str) -> IResult<&'a str, O>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>> {
+    separated_list1(pair(line_ending, line_ending), item)
+}
+
+/// A label ending in a colon on its own line, then one item per line, like
+/// `seed-to-soil map:` and its ranges
+pub fn labelled_block<'a, L, O>(
+    label: impl FnMut(&'a str) -> IResult<&'a str, L>,
+    item: impl FnMut(&'a str) -> IResult<&'a str, O>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, (L, Vec<O>)> {
+    separated_pair(label, pair(char(':'), line_ending), lines(item))
+}
+
+/// Lines of cells up to a blank line or the end, `cell` giving `None` for a
+/// character that has no business being on the map
+pub fn grid<'a, T: Clone>(
+    cell: impl Fn(char) -> Option<T>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, Grid<T>> {
+    move |input| {
+        let row = many1(map_opt(none_of("\r\n"), &cell));
+        map_res(separated_list1(line_ending, row), Grid::from_rows)(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nom::{bytes::complete::tag, character::complete::alpha1, sequence::preceded};
+
+    #[test]
+    fn test_numbers() {
+        assert_eq!(unsigned::<u32>("42 rest"), Ok((" rest", 42)));
+        assert_eq!(signed::<i64>("-17,"), Ok((",", -17)));
+        assert_eq!(signed::<i64>("+3"), Ok(("", 3)));
+        assert!(unsigned::<u32>("-17").is_err());
+        assert!(unsigned::<u8>("256").is_err());
+    }
+
+    #[test]
+    fn test_lists() {
+        assert_eq!(
+            comma_list(unsigned::<u8>)("1,2, 3 x"),
+            Ok((" x", vec![1, 2, 3]))
+        );
+        assert_eq!(
+            space_list(signed::<i8>)("1   -2 3\n4"),
+            Ok(("\n4", vec![1, -2, 3]))
+        );
+        assert_eq!(
+            lines(unsigned::<u8>)("1\n2\n\n3"),
+            Ok(("\n\n3", vec![1, 2]))
+        );
+        assert_eq!(
+            blocks(lines(unsigned::<u8>))("1\n2\n\n3"),
+            Ok(("", vec![vec![1, 2], vec![3]]))
+        );
+    }
+
+    #[test]
+    fn test_key_value() {
+        assert_eq!(
+            key_value(alpha1, space_list(unsigned::<u32>))("Time:      7  15   30"),
+            Ok(("", ("Time", vec![7, 15, 30])))
+        );
+    }
+
+    #[test]
+    fn test_labelled_block() {
+        let text = "seed-to-soil map:\n50 98 2\n52 50 48\n\nsoil-to-fertilizer map:\n0 15 37";
+        let mut mapping = labelled_block(
+            terminated(recognize(pair(alpha1, tag("-to-soil"))), tag(" map")),
+            space_list(unsigned::<u64>),
+        );
+
+        let (rest, (label, ranges)) = mapping(text).unwrap();
+        assert_eq!(label, "seed-to-soil");
+        assert_eq!(ranges, [vec![50, 98, 2], vec![52, 50, 48]]);
+        assert!(rest.starts_with("\n\nsoil"));
+    }
+
+    #[test]
+    fn test_grid() {
+        let wall = |c| match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        };
+
+        let (rest, parsed) = grid(wall)("#.\n.#\n\nmoves").unwrap();
+        assert_eq!(rest, "\n\nmoves");
+        assert_eq!(
+            parsed,
+            Grid::parse_with(&["#.", ".#"], |c| c == '#').unwrap()
+        );
+
+        assert!(grid(wall)("#.\n.").is_err());
+        assert!(parse_all("#.\n.x", grid(wall)).is_err());
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let game = |input| preceded(tag("Game "), unsigned::<u32>)(input);
+
+        assert_eq!(parse_all("Game 7\n", game), Ok(7));
+
+        let err = parse_all("Game 7 blue", game).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 1, column 8: expected the end of the input\nGame 7 blue\n       ^"
+        );
+
+        let err = parse_each_line("Game 1\nGame 2\nGame x", game).unwrap_err();
+        assert_eq!((err.line, err.column), (3, 6));
+        assert_eq!(
+            err.to_string(),
+            "line 3, column 6: expected a number\nGame x\n     ^"
+        );
+    }
+
+    #[test]
+    fn test_parse_error_in_later_line() {
+        let err = parse_all("1 2\n3 y\n", lines(space_list(unsigned::<u8>))).unwrap_err();
+
+        assert_eq!((err.line, err.column), (2, 3));
+        assert_eq!(err.text, "3 y");
+    }
+}
@@ -0,0 +1,433 @@
+use std::fmt;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+/// A position or offset on a 2D map, `y` going down like the puzzle input
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point2 {
+    pub x: isize,
+    pub y: isize,
+}
+
+/// A position or offset in 3D space
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point3 {
+    pub x: isize,
+    pub y: isize,
+    pub z: isize,
+}
+
+impl Point2 {
+    pub const ORIGIN: Point2 = Point2 { x: 0, y: 0 };
+
+    pub const fn new(x: isize, y: isize) -> Self {
+        Point2 { x, y }
+    }
+
+    pub fn manhattan(self, other: Point2) -> usize {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+
+    /// The distance when diagonal steps count as one, like a king in chess
+    pub fn chebyshev(self, other: Point2) -> usize {
+        self.x.abs_diff(other.x).max(self.y.abs_diff(other.y))
+    }
+
+    /// Rotates a quarter turn clockwise around the origin, as seen on the map
+    pub fn turn_right(self) -> Self {
+        Point2::new(-self.y, self.x)
+    }
+
+    /// Rotates a quarter turn anticlockwise around the origin, as seen on the map
+    pub fn turn_left(self) -> Self {
+        Point2::new(self.y, -self.x)
+    }
+
+    /// Cuts an offset down to at most one step along each axis
+    pub fn signum(self) -> Self {
+        Point2::new(self.x.signum(), self.y.signum())
+    }
+
+    pub fn neighbours4(self) -> impl Iterator<Item = Point2> {
+        Direction4::ALL.into_iter().map(move |d| self + d)
+    }
+
+    pub fn neighbours8(self) -> impl Iterator<Item = Point2> {
+        Direction8::ALL.into_iter().map(move |d| self + d)
+    }
+}
+
+impl Point3 {
+    pub const ORIGIN: Point3 = Point3 { x: 0, y: 0, z: 0 };
+
+    pub const fn new(x: isize, y: isize, z: isize) -> Self {
+        Point3 { x, y, z }
+    }
+
+    pub fn manhattan(self, other: Point3) -> usize {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y) + self.z.abs_diff(other.z)
+    }
+
+    pub fn chebyshev(self, other: Point3) -> usize {
+        self.x
+            .abs_diff(other.x)
+            .max(self.y.abs_diff(other.y))
+            .max(self.z.abs_diff(other.z))
+    }
+
+    /// The six points sharing a face with this one
+    pub fn neighbours6(self) -> impl Iterator<Item = Point3> {
+        [
+            Point3::new(1, 0, 0),
+            Point3::new(-1, 0, 0),
+            Point3::new(0, 1, 0),
+            Point3::new(0, -1, 0),
+            Point3::new(0, 0, 1),
+            Point3::new(0, 0, -1),
+        ]
+        .into_iter()
+        .map(move |offset| self + offset)
+    }
+}
+
+macro_rules! impl_ops {
+    ($point:ident { $($field:ident),+ }) => {
+        impl Add for $point {
+            type Output = $point;
+
+            fn add(self, other: $point) -> $point {
+                $point { $($field: self.$field + other.$field),+ }
+            }
+        }
+
+        impl Sub for $point {
+            type Output = $point;
+
+            fn sub(self, other: $point) -> $point {
+                $point { $($field: self.$field - other.$field),+ }
+            }
+        }
+
+        impl Mul<isize> for $point {
+            type Output = $point;
+
+            fn mul(self, scale: isize) -> $point {
+                $point { $($field: self.$field * scale),+ }
+            }
+        }
+
+        impl Neg for $point {
+            type Output = $point;
+
+            fn neg(self) -> $point {
+                $point { $($field: -self.$field),+ }
+            }
+        }
+
+        impl AddAssign for $point {
+            fn add_assign(&mut self, other: $point) {
+                *self = *self + other;
+            }
+        }
+
+        impl SubAssign for $point {
+            fn sub_assign(&mut self, other: $point) {
+                *self = *self - other;
+            }
+        }
+    };
+}
+
+impl_ops!(Point2 { x, y });
+impl_ops!(Point3 { x, y, z });
+
+impl From<(isize, isize)> for Point2 {
+    fn from((x, y): (isize, isize)) -> Self {
+        Point2::new(x, y)
+    }
+}
+
+impl From<Point2> for (isize, isize) {
+    fn from(point: Point2) -> Self {
+        (point.x, point.y)
+    }
+}
+
+impl From<(isize, isize, isize)> for Point3 {
+    fn from((x, y, z): (isize, isize, isize)) -> Self {
+        Point3::new(x, y, z)
+    }
+}
+
+impl fmt::Display for Point2 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{},{}", self.x, self.y)
+    }
+}
+
+impl fmt::Display for Point3 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{},{},{}", self.x, self.y, self.z)
+    }
+}
+
+/// A character that isn't one of `^v<>`, `NESW` or `UDLR`
+#[derive(Debug, PartialEq)]
+pub struct UnknownDirection(pub char);
+
+impl fmt::Display for UnknownDirection {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "`{}` is not a direction", self.0)
+    }
+}
+
+impl std::error::Error for UnknownDirection {}
+
+/// The four ways to move on a map without going diagonally
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction4 {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction4 {
+    /// Clockwise from up
+    pub const ALL: [Direction4; 4] = [
+        Direction4::Up,
+        Direction4::Right,
+        Direction4::Down,
+        Direction4::Left,
+    ];
+
+    pub fn offset(self) -> Point2 {
+        match self {
+            Direction4::Up => Point2::new(0, -1),
+            Direction4::Right => Point2::new(1, 0),
+            Direction4::Down => Point2::new(0, 1),
+            Direction4::Left => Point2::new(-1, 0),
+        }
+    }
+
+    pub fn turn_right(self) -> Self {
+        Direction4::ALL[(self as usize + 1) % 4]
+    }
+
+    pub fn turn_left(self) -> Self {
+        Direction4::ALL[(self as usize + 3) % 4]
+    }
+
+    pub fn reverse(self) -> Self {
+        Direction4::ALL[(self as usize + 2) % 4]
+    }
+
+    pub fn is_vertical(self) -> bool {
+        matches!(self, Direction4::Up | Direction4::Down)
+    }
+}
+
+impl TryFrom<char> for Direction4 {
+    type Error = UnknownDirection;
+
+    /// Reads `^v<>`, `NESW` and `UDLR`, upper or lower case
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        match c.to_ascii_uppercase() {
+            '^' | 'N' | 'U' => Ok(Direction4::Up),
+            '>' | 'E' | 'R' => Ok(Direction4::Right),
+            'V' | 'S' | 'D' => Ok(Direction4::Down),
+            '<' | 'W' | 'L' => Ok(Direction4::Left),
+            _ => Err(UnknownDirection(c)),
+        }
+    }
+}
+
+impl From<Direction4> for char {
+    fn from(direction: Direction4) -> char {
+        match direction {
+            Direction4::Up => '^',
+            Direction4::Right => '>',
+            Direction4::Down => 'v',
+            Direction4::Left => '<',
+        }
+    }
+}
+
+/// The eight ways to move on a map, diagonals included
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction8 {
+    N,
+    NE,
+    E,
+    SE,
+    S,
+    SW,
+    W,
+    NW,
+}
+
+impl Direction8 {
+    /// Clockwise from north
+    pub const ALL: [Direction8; 8] = [
+        Direction8::N,
+        Direction8::NE,
+        Direction8::E,
+        Direction8::SE,
+        Direction8::S,
+        Direction8::SW,
+        Direction8::W,
+        Direction8::NW,
+    ];
+
+    pub fn offset(self) -> Point2 {
+        match self {
+            Direction8::N => Point2::new(0, -1),
+            Direction8::NE => Point2::new(1, -1),
+            Direction8::E => Point2::new(1, 0),
+            Direction8::SE => Point2::new(1, 1),
+            Direction8::S => Point2::new(0, 1),
+            Direction8::SW => Point2::new(-1, 1),
+            Direction8::W => Point2::new(-1, 0),
+            Direction8::NW => Point2::new(-1, -1),
+        }
+    }
+
+    /// An eighth of a turn clockwise
+    pub fn turn_right(self) -> Self {
+        Direction8::ALL[(self as usize + 1) % 8]
+    }
+
+    /// An eighth of a turn anticlockwise
+    pub fn turn_left(self) -> Self {
+        Direction8::ALL[(self as usize + 7) % 8]
+    }
+
+    pub fn reverse(self) -> Self {
+        Direction8::ALL[(self as usize + 4) % 8]
+    }
+
+    pub fn is_diagonal(self) -> bool {
+        self as usize % 2 == 1
+    }
+}
+
+impl From<Direction4> for Direction8 {
+    fn from(direction: Direction4) -> Self {
+        Direction8::ALL[direction as usize * 2]
+    }
+}
+
+impl TryFrom<char> for Direction8 {
+    type Error = UnknownDirection;
+
+    /// Reads the same characters as `Direction4`, which are all straight
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        Direction4::try_from(c).map(Direction8::from)
+    }
+}
+
+impl Add<Direction4> for Point2 {
+    type Output = Point2;
+
+    fn add(self, direction: Direction4) -> Point2 {
+        self + direction.offset()
+    }
+}
+
+impl Add<Direction8> for Point2 {
+    type Output = Point2;
+
+    fn add(self, direction: Direction8) -> Point2 {
+        self + direction.offset()
+    }
+}
+
+impl AddAssign<Direction4> for Point2 {
+    fn add_assign(&mut self, direction: Direction4) {
+        *self = *self + direction;
+    }
+}
+
+impl AddAssign<Direction8> for Point2 {
+    fn add_assign(&mut self, direction: Direction8) {
+        *self = *self + direction;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_arithmetic() {
+        let a = Point2::new(1, 2);
+        let b = Point2::new(4, -2);
+
+        assert_eq!(a + b, Point2::new(5, 0));
+        assert_eq!(a - b, Point2::new(-3, 4));
+        assert_eq!(b * 3, Point2::new(12, -6));
+        assert_eq!(-a, Point2::new(-1, -2));
+        assert_eq!((b - a).signum(), Point2::new(1, -1));
+        assert_eq!(
+            Point3::new(1, 2, 3) + Point3::new(1, 1, 1),
+            Point3::new(2, 3, 4)
+        );
+    }
+
+    #[test]
+    fn test_distances() {
+        let a = Point2::new(1, 2);
+        let b = Point2::new(4, -2);
+
+        assert_eq!(a.manhattan(b), 7);
+        assert_eq!(a.chebyshev(b), 4);
+        assert_eq!(Point3::ORIGIN.manhattan(Point3::new(-1, 2, -3)), 6);
+        assert_eq!(Point3::ORIGIN.chebyshev(Point3::new(-1, 2, -3)), 3);
+    }
+
+    #[test]
+    fn test_turns() {
+        assert_eq!(Direction4::Up.turn_right(), Direction4::Right);
+        assert_eq!(Direction4::Up.turn_left(), Direction4::Left);
+        assert_eq!(Direction4::Left.reverse(), Direction4::Right);
+        assert_eq!(Direction8::N.turn_left(), Direction8::NW);
+        assert_eq!(Direction8::SW.turn_right(), Direction8::W);
+
+        for direction in Direction4::ALL {
+            assert_eq!(
+                direction.offset().turn_right(),
+                direction.turn_right().offset()
+            );
+            assert_eq!(
+                direction.offset().turn_left(),
+                direction.turn_left().offset()
+            );
+            assert_eq!(Direction8::from(direction).offset(), direction.offset());
+        }
+    }
+
+    #[test]
+    fn test_directions_from_chars() {
+        let parsed = "^>v<NESWurdl"
+            .chars()
+            .map(Direction4::try_from)
+            .collect::<Result<Vec<_>, _>>();
+
+        assert_eq!(
+            parsed,
+            Ok([Direction4::ALL; 3].concat().into_iter().collect())
+        );
+        assert_eq!(Direction4::try_from('x'), Err(UnknownDirection('x')));
+        assert_eq!(Direction8::try_from('<'), Ok(Direction8::W));
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let mut point = Point2::new(3, 3);
+        point += Direction4::Down;
+
+        assert_eq!(point, Point2::new(3, 4));
+        assert_eq!(point.neighbours4().count(), 4);
+        assert!(point.neighbours8().all(|n| n.chebyshev(point) == 1));
+        assert_eq!(Point3::ORIGIN.neighbours6().count(), 6);
+    }
+}
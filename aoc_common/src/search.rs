@@ -0,0 +1,504 @@
+//! Searches over any state type. A state is whatever the puzzle needs to know
+//! where it is, a position or a `(Point2, Direction4)` for something that has
+//! to turn, and the caller says how to get from one state to the next.
+
+use std::cmp::{Ordering, Reverse};
+use std::collections::hash_map::Entry;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+/// What a step can cost, `Default` is taken to be zero
+pub trait Cost: Copy + Ord + Add<Output = Self> + Default {}
+
+impl<C: Copy + Ord + Add<Output = C> + Default> Cost for C {}
+
+/// Every state that can be reached from the start, the start included
+pub fn reachable<S, I>(start: S, mut neighbours: impl FnMut(&S) -> I) -> HashSet<S>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut seen = HashSet::from([start.clone()]);
+    let mut pending = vec![start];
+
+    while let Some(state) = pending.pop() {
+        for next in neighbours(&state) {
+            if seen.insert(next.clone()) {
+                pending.push(next);
+            }
+        }
+    }
+
+    seen
+}
+
+/// The fewest steps from the start to a goal, as the path of every state on
+/// the way from the start to the goal
+pub fn bfs<S, I>(
+    start: S,
+    mut neighbours: impl FnMut(&S) -> I,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Option<Vec<S>>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut came_from: HashMap<S, Option<S>> = HashMap::from([(start.clone(), None)]);
+    let mut pending = VecDeque::from([start]);
+
+    while let Some(state) = pending.pop_front() {
+        if is_goal(&state) {
+            return Some(walk_back(&came_from, state));
+        }
+
+        for next in neighbours(&state) {
+            if let Entry::Vacant(entry) = came_from.entry(next.clone()) {
+                entry.insert(Some(state.clone()));
+                pending.push_back(next);
+            }
+        }
+    }
+
+    None
+}
+
+fn walk_back<S: Clone + Eq + Hash>(came_from: &HashMap<S, Option<S>>, goal: S) -> Vec<S> {
+    let mut path = vec![goal];
+
+    while let Some(Some(previous)) = path.last().and_then(|s| came_from.get(s)) {
+        path.push(previous.clone());
+    }
+
+    path.reverse();
+    path
+}
+
+/// The cheapest way from the start to a goal, with what it cost
+pub fn dijkstra<S, C, I>(
+    start: S,
+    neighbours: impl FnMut(&S) -> I,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Option<(Vec<S>, C)>
+where
+    S: Clone + Eq + Hash,
+    C: Cost,
+    I: IntoIterator<Item = (S, C)>,
+{
+    astar(start, neighbours, |_| C::default(), is_goal)
+}
+
+/// Dijkstra guided by an estimate of the cost left to a goal. The estimate must
+/// never be more than the real cost or the path found might not be the cheapest.
+pub fn astar<S, C, I>(
+    start: S,
+    mut neighbours: impl FnMut(&S) -> I,
+    mut estimate: impl FnMut(&S) -> C,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Option<(Vec<S>, C)>
+where
+    S: Clone + Eq + Hash,
+    C: Cost,
+    I: IntoIterator<Item = (S, C)>,
+{
+    let mut states = Indexed::default();
+    let mut best: Vec<(C, Option<usize>)> = Vec::new();
+    let mut pending = BinaryHeap::new();
+
+    states.index(start.clone());
+    best.push((C::default(), None));
+    pending.push(Reverse((estimate(&start), C::default(), 0)));
+
+    while let Some(Reverse((_, cost, ix))) = pending.pop() {
+        if cost > best[ix].0 {
+            continue;
+        }
+
+        let state = states.get(ix).clone();
+        if is_goal(&state) {
+            let mut path = vec![ix];
+            while let Some(previous) = best[*path.last().expect("Path has the goal")].1 {
+                path.push(previous);
+            }
+            let path = path.into_iter().rev().map(|ix| states.get(ix).clone());
+            return Some((path.collect(), cost));
+        }
+
+        for (next, step) in neighbours(&state) {
+            let next_cost = cost + step;
+            let next_estimate = next_cost + estimate(&next);
+            let next_ix = states.index(next);
+
+            if next_ix == best.len() {
+                best.push((next_cost, Some(ix)));
+            } else if next_cost < best[next_ix].0 {
+                best[next_ix] = (next_cost, Some(ix));
+            } else {
+                continue;
+            }
+            pending.push(Reverse((next_estimate, next_cost, next_ix)));
+        }
+    }
+
+    None
+}
+
+/// Every state reachable from the start with the fewest steps to it, keeping
+/// every predecessor that ties so all the shortest paths can be found
+pub fn bfs_all<S, I>(start: S, mut neighbours: impl FnMut(&S) -> I) -> ShortestPaths<S, usize>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut paths = ShortestPaths::new(start);
+    let mut pending = VecDeque::from([0]);
+
+    while let Some(ix) = pending.pop_front() {
+        let cost = paths.costs[ix] + 1;
+
+        for next in neighbours(&paths.states.get(ix).clone()) {
+            if let (next_ix, Relaxed::New) = paths.relax(ix, next, cost) {
+                pending.push_back(next_ix);
+            }
+        }
+    }
+
+    paths
+}
+
+/// Every state reachable from the start with the cheapest cost to it, keeping
+/// every predecessor that ties so all the cheapest paths can be found
+pub fn dijkstra_all<S, C, I>(start: S, mut neighbours: impl FnMut(&S) -> I) -> ShortestPaths<S, C>
+where
+    S: Clone + Eq + Hash,
+    C: Cost,
+    I: IntoIterator<Item = (S, C)>,
+{
+    let mut paths = ShortestPaths::new(start);
+    let mut pending = BinaryHeap::from([Reverse((C::default(), 0))]);
+
+    while let Some(Reverse((cost, ix))) = pending.pop() {
+        if cost > paths.costs[ix] {
+            continue;
+        }
+
+        for (next, step) in neighbours(&paths.states.get(ix).clone()) {
+            let next_cost = cost + step;
+            if let (next_ix, Relaxed::New | Relaxed::Cheaper) = paths.relax(ix, next, next_cost) {
+                pending.push(Reverse((next_cost, next_ix)));
+            }
+        }
+    }
+
+    paths
+}
+
+/// Gives each state a number so the searches can keep everything else in
+/// vectors and never need the state to be `Ord`
+struct Indexed<S> {
+    index: HashMap<S, usize>,
+    states: Vec<S>,
+}
+
+impl<S> Default for Indexed<S> {
+    fn default() -> Self {
+        Indexed {
+            index: HashMap::new(),
+            states: Vec::new(),
+        }
+    }
+}
+
+impl<S: Clone + Eq + Hash> Indexed<S> {
+    fn index(&mut self, state: S) -> usize {
+        match self.index.entry(state) {
+            Entry::Occupied(entry) => *entry.get(),
+            Entry::Vacant(entry) => {
+                self.states.push(entry.key().clone());
+                *entry.insert(self.states.len() - 1)
+            }
+        }
+    }
+
+    fn get(&self, ix: usize) -> &S {
+        &self.states[ix]
+    }
+}
+
+/// How a newly found way to a state compares with the best one so far
+enum Relaxed {
+    New,
+    Cheaper,
+    Tied,
+    Worse,
+}
+
+/// The cheapest cost to every state found by `bfs_all` or `dijkstra_all`,
+/// along with every predecessor on a cheapest path to it
+pub struct ShortestPaths<S, C> {
+    start: usize,
+    states: Indexed<S>,
+    costs: Vec<C>,
+    predecessors: Vec<Vec<usize>>,
+}
+
+impl<S: Clone + Eq + Hash, C: Cost> ShortestPaths<S, C> {
+    fn new(start: S) -> Self {
+        let mut states = Indexed::default();
+        states.index(start);
+
+        ShortestPaths {
+            start: 0,
+            states,
+            costs: vec![C::default()],
+            predecessors: vec![Vec::new()],
+        }
+    }
+
+    /// Records a way to the state from `from`, returning the state's index
+    fn relax(&mut self, from: usize, state: S, cost: C) -> (usize, Relaxed) {
+        let ix = self.states.index(state);
+
+        if ix == self.costs.len() {
+            self.costs.push(cost);
+            self.predecessors.push(vec![from]);
+            return (ix, Relaxed::New);
+        }
+
+        let relaxed = match cost.cmp(&self.costs[ix]) {
+            Ordering::Less => {
+                self.costs[ix] = cost;
+                self.predecessors[ix] = vec![from];
+                Relaxed::Cheaper
+            }
+            Ordering::Equal if ix != self.start => {
+                if !self.predecessors[ix].contains(&from) {
+                    self.predecessors[ix].push(from);
+                }
+                Relaxed::Tied
+            }
+            Ordering::Equal | Ordering::Greater => Relaxed::Worse,
+        };
+
+        (ix, relaxed)
+    }
+
+    /// The cheapest cost to the state, `None` when it can't be reached
+    pub fn cost(&self, state: &S) -> Option<C> {
+        self.states.index.get(state).map(|&ix| self.costs[ix])
+    }
+
+    /// Every state that was reached with its cheapest cost
+    pub fn iter(&self) -> impl Iterator<Item = (&S, C)> {
+        self.states.states.iter().zip(self.costs.iter().copied())
+    }
+
+    /// The states just before this one on each of its cheapest paths
+    pub fn predecessors(&self, state: &S) -> impl Iterator<Item = &S> {
+        self.states
+            .index
+            .get(state)
+            .into_iter()
+            .flat_map(|&ix| &self.predecessors[ix])
+            .map(|&ix| self.states.get(ix))
+    }
+
+    /// One of the cheapest paths from the start to the goal
+    pub fn path_to(&self, goal: &S) -> Option<Vec<S>> {
+        let mut ix = *self.states.index.get(goal)?;
+        let mut path = vec![goal.clone()];
+
+        while let Some(&previous) = self.predecessors[ix].first() {
+            path.push(self.states.get(previous).clone());
+            ix = previous;
+        }
+
+        path.reverse();
+        Some(path)
+    }
+
+    /// Every tied cheapest path from the start to the goal. There can be a lot
+    /// of them, `states_on_paths_to` is cheaper when only the states matter.
+    pub fn all_paths_to(&self, goal: &S) -> Vec<Vec<S>> {
+        let Some(&goal) = self.states.index.get(goal) else {
+            return Vec::new();
+        };
+
+        let mut paths = Vec::new();
+        let mut pending = vec![vec![goal]];
+
+        while let Some(path) = pending.pop() {
+            let last = *path.last().expect("Paths are never empty");
+            if self.predecessors[last].is_empty() {
+                let states = path.iter().rev().map(|&ix| self.states.get(ix).clone());
+                paths.push(states.collect());
+                continue;
+            }
+
+            for &previous in &self.predecessors[last] {
+                let mut longer = path.clone();
+                longer.push(previous);
+                pending.push(longer);
+            }
+        }
+
+        paths
+    }
+
+    /// Every state on any of the cheapest paths to any of the goals
+    pub fn states_on_paths_to<'a>(&self, goals: impl IntoIterator<Item = &'a S>) -> HashSet<S>
+    where
+        S: 'a,
+    {
+        let mut seen = HashSet::new();
+        let mut pending = goals
+            .into_iter()
+            .filter_map(|goal| self.states.index.get(goal).copied())
+            .collect::<Vec<_>>();
+
+        while let Some(ix) = pending.pop() {
+            if seen.insert(ix) {
+                pending.extend(&self.predecessors[ix]);
+            }
+        }
+
+        seen.into_iter()
+            .map(|ix| self.states.get(ix).clone())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Direction4, Grid, Point2};
+
+    const MAZE: &str = "S..#\n.#..\n...E";
+
+    fn maze() -> Grid<char> {
+        Grid::parse(&MAZE.lines().map(String::from).collect::<Vec<_>>()).unwrap()
+    }
+
+    fn open_neighbours(grid: &Grid<char>, pos: Point2) -> Vec<Point2> {
+        pos.neighbours4()
+            .filter(|&p| grid.get(p).is_some_and(|&c| c != '#'))
+            .collect()
+    }
+
+    #[test]
+    fn test_reachable() {
+        let grid = maze();
+        let start = Point2::new(0, 0);
+
+        assert_eq!(reachable(start, |&p| open_neighbours(&grid, p)).len(), 10);
+    }
+
+    #[test]
+    fn test_bfs() {
+        let grid = maze();
+        let end = Point2::new(3, 2);
+
+        let path = bfs(
+            Point2::ORIGIN,
+            |&p| open_neighbours(&grid, p),
+            |&p| p == end,
+        )
+        .unwrap();
+
+        assert_eq!(path.len() - 1, 5);
+        assert_eq!(path.first(), Some(&Point2::ORIGIN));
+        assert_eq!(path.last(), Some(&end));
+        assert_eq!(
+            bfs(Point2::ORIGIN, |&p| open_neighbours(&grid, p), |&p| p.x > 5),
+            None
+        );
+    }
+
+    #[test]
+    fn test_dijkstra_and_astar_agree() {
+        let grid = Grid::parse_with(&["1163", "1381", "2136"].map(String::from), |c| {
+            c.to_digit(10).unwrap() as usize
+        })
+        .unwrap();
+        let end = Point2::new(3, 2);
+        let neighbours = |&p: &Point2| {
+            p.neighbours4()
+                .filter_map(|n| grid.get(n).map(|&risk| (n, risk)))
+                .collect::<Vec<_>>()
+        };
+
+        let (path, cost) = dijkstra(Point2::ORIGIN, neighbours, |&p| p == end).unwrap();
+        assert_eq!(cost, 13);
+        assert_eq!(path.last(), Some(&end));
+
+        let (_, astar_cost) = astar(
+            Point2::ORIGIN,
+            neighbours,
+            |&p| p.manhattan(end),
+            |&p| p == end,
+        )
+        .unwrap();
+        assert_eq!(astar_cost, cost);
+    }
+
+    /// Moving costs 1 and turning costs 1000 like the reindeer maze, the two
+    /// ways round the wall cost the same but finish facing different ways
+    #[test]
+    fn test_all_paths_with_facing() {
+        let grid = Grid::parse(&["...", ".#.", "..."].map(String::from)).unwrap();
+        let start = (Point2::new(1, 0), Direction4::Down);
+        let end = Point2::new(1, 2);
+
+        let paths = dijkstra_all(start, |&(pos, facing)| {
+            let forward = pos + facing;
+            let step = grid
+                .get(forward)
+                .filter(|&&c| c != '#')
+                .map(|_| ((forward, facing), 1));
+            [
+                step,
+                Some(((pos, facing.turn_left()), 1000)),
+                Some(((pos, facing.turn_right()), 1000)),
+            ]
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>()
+        });
+
+        let goals = Direction4::ALL.map(|facing| (end, facing));
+        let best = goals.iter().filter_map(|g| paths.cost(g)).min();
+        assert_eq!(best, Some(3004));
+
+        let best_goals = goals
+            .iter()
+            .filter(|g| paths.cost(g) == best)
+            .collect::<Vec<_>>();
+        assert_eq!(best_goals.len(), 2);
+        assert_eq!(paths.all_paths_to(best_goals[0]).len(), 1);
+
+        let tiles = paths
+            .states_on_paths_to(best_goals)
+            .into_iter()
+            .map(|(pos, _)| pos)
+            .collect::<HashSet<_>>();
+        assert_eq!(tiles.len(), 8);
+    }
+
+    #[test]
+    fn test_bfs_all() {
+        let grid = Grid::parse(&["...", "...", "..."].map(String::from)).unwrap();
+        let corner = Point2::new(2, 2);
+
+        let paths = bfs_all(Point2::ORIGIN, |&p| {
+            p.neighbours4()
+                .filter(|&n| grid.in_bounds(n))
+                .collect::<Vec<_>>()
+        });
+
+        assert_eq!(paths.cost(&corner), Some(4));
+        assert_eq!(paths.all_paths_to(&corner).len(), 6);
+        assert_eq!(paths.path_to(&corner).map(|p| p.len()), Some(5));
+        assert_eq!(paths.predecessors(&corner).count(), 2);
+        assert_eq!(paths.iter().map(|(_, cost)| cost).max(), Some(4));
+    }
+}
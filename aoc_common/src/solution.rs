@@ -0,0 +1,304 @@
+//! The shape every day's solution takes, so the answers can be had from code
+//! as well as from the day's binary.
+
+use std::fmt;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use crate::input;
+
+/// Set by the runner to ask for only one part, `1` or `2`
+pub const PART_VAR: &str = "AOC_PART";
+
+/// Set to `answers` by the runner to get each answer on its own line, without
+/// the sentence around it, to `json` to get the answers and how long each step
+/// took as one JSON object, or to `bench` to get timings instead of answers
+pub const FORMAT_VAR: &str = "AOC_FORMAT";
+
+/// How many times `bench` times each step
+pub const ITERATIONS_VAR: &str = "AOC_ITERATIONS";
+
+/// A day's answer, compared as a value rather than as printed text
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Answer {
+    Number(i128),
+    Text(String),
+    /// The part has not been solved yet
+    Unsolved,
+}
+
+macro_rules! impl_from_number {
+    ($($t:ty),*) => {
+        $(impl From<$t> for Answer {
+            fn from(n: $t) -> Self {
+                Answer::Number(n as i128)
+            }
+        })*
+    };
+}
+
+impl_from_number!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, usize);
+
+impl From<String> for Answer {
+    fn from(text: String) -> Self {
+        Answer::Text(text)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(text: &str) -> Self {
+        Answer::Text(text.to_string())
+    }
+}
+
+/// `None` for a search that came up empty
+impl<T: Into<Answer>> From<Option<T>> for Answer {
+    fn from(answer: Option<T>) -> Self {
+        answer.map_or(Answer::Unsolved, Into::into)
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Answer::Number(n) => write!(f, "{n}"),
+            Answer::Text(text) => write!(f, "{text}"),
+            Answer::Unsolved => write!(f, "unsolved"),
+        }
+    }
+}
+
+/// A day's puzzle. The input is parsed once and shared by both parts.
+pub trait Solution {
+    type Input;
+
+    fn parse(input: &str) -> Self::Input;
+    fn part1(input: &Self::Input) -> Answer;
+    fn part2(input: &Self::Input) -> Answer;
+}
+
+/// Answers the day's puzzle for the input on stdin, normalised by
+/// `input::read`, putting each answer in place of the `{}` in its part's
+/// sentence. This is all a day's `main` does.
+pub fn run<S: Solution>(sentences: [&str; 2]) {
+    let format = std::env::var(FORMAT_VAR).unwrap_or_default();
+
+    let text = input::read(None).expect("The input should be readable text");
+
+    let parts = parts_asked_for(std::env::var(PART_VAR).ok().as_deref());
+
+    if format == "bench" {
+        let iterations = std::env::var(ITERATIONS_VAR)
+            .ok()
+            .and_then(|n| n.parse().ok())
+            .unwrap_or(1);
+        for (step, samples) in bench::<S>(&text, parts, iterations) {
+            let nanos = samples
+                .iter()
+                .map(|d| d.as_nanos().to_string())
+                .collect::<Vec<_>>();
+            println!("{step} {}", nanos.join(" "));
+        }
+        return;
+    }
+
+    if format == "json" {
+        println!("{}", json::<S>(&text, parts));
+        return;
+    }
+
+    let input = S::parse(&text);
+
+    for (part, sentence) in [1, 2].into_iter().zip(sentences) {
+        if !parts[part - 1] {
+            continue;
+        }
+
+        let answer = match part {
+            1 => S::part1(&input),
+            _ => S::part2(&input),
+        };
+        match format.as_str() {
+            "answers" => println!("{answer}"),
+            _ => println!("{}", sentence.replacen("{}", &answer.to_string(), 1)),
+        }
+    }
+}
+
+/// Times parsing and each part asked for, `iterations` times over
+pub fn bench<S: Solution>(
+    text: &str,
+    parts: [bool; 2],
+    iterations: usize,
+) -> Vec<(&'static str, Vec<Duration>)> {
+    let mut steps = vec![("parse", Vec::new())];
+    for (part, name) in [(0, "part1"), (1, "part2")] {
+        if parts[part] {
+            steps.push((name, Vec::new()));
+        }
+    }
+
+    for _ in 0..iterations {
+        let started = Instant::now();
+        let input = black_box(S::parse(black_box(text)));
+        steps[0].1.push(started.elapsed());
+
+        for (name, samples) in steps.iter_mut().skip(1) {
+            let started = Instant::now();
+            black_box(match *name {
+                "part1" => S::part1(&input),
+                _ => S::part2(&input),
+            });
+            samples.push(started.elapsed());
+        }
+    }
+
+    steps
+}
+
+/// The answers to the parts asked for and the milliseconds each step took,
+/// `null` for a part that was not asked for or is unsolved
+pub fn json<S: Solution>(text: &str, parts: [bool; 2]) -> String {
+    let mut timings = Vec::new();
+
+    let started = Instant::now();
+    let input = S::parse(text);
+    timings.push(("parse", started.elapsed()));
+
+    let mut answers = [None, None];
+    for (part, name) in [(0, "part1"), (1, "part2")] {
+        if !parts[part] {
+            continue;
+        }
+        let started = Instant::now();
+        answers[part] = Some(match part {
+            0 => S::part1(&input),
+            _ => S::part2(&input),
+        });
+        timings.push((name, started.elapsed()));
+    }
+
+    let answers = answers.map(|answer| match answer {
+        Some(Answer::Unsolved) | None => "null".to_string(),
+        Some(answer) => json_string(&answer.to_string()),
+    });
+    let timings = timings
+        .iter()
+        .map(|(step, took)| format!("\"{step}\":{}", took.as_secs_f64() * 1000.0))
+        .collect::<Vec<_>>();
+
+    format!(
+        "{{\"part1\":{},\"part2\":{},\"timings_ms\":{{{}}}}}",
+        answers[0],
+        answers[1],
+        timings.join(",")
+    )
+}
+
+/// Quotes text as a JSON string
+fn json_string(text: &str) -> String {
+    let mut quoted = String::from('"');
+    for c in text.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            c if c.is_control() => quoted.push_str(&format!("\\u{:04x}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// Both parts unless the runner asked for just one
+fn parts_asked_for(part: Option<&str>) -> [bool; 2] {
+    match part.map(str::trim) {
+        Some("1") => [true, false],
+        Some("2") => [false, true],
+        _ => [true, true],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_answer_from() {
+        assert_eq!(Answer::from(42u64), Answer::Number(42));
+        assert_eq!(Answer::from(-3i32), Answer::Number(-3));
+        assert_eq!(Answer::from("4,3,0"), Answer::Text("4,3,0".to_string()));
+        assert_eq!(Answer::from(Some(7usize)), Answer::Number(7));
+        assert_eq!(Answer::from(None::<usize>), Answer::Unsolved);
+    }
+
+    #[test]
+    fn test_answer_display() {
+        assert_eq!(Answer::from(u64::MAX).to_string(), "18446744073709551615");
+        assert_eq!(Answer::from("abc").to_string(), "abc");
+        assert_eq!(Answer::Unsolved.to_string(), "unsolved");
+    }
+
+    #[test]
+    fn test_parts_asked_for() {
+        assert_eq!(parts_asked_for(None), [true, true]);
+        assert_eq!(parts_asked_for(Some("1")), [true, false]);
+        assert_eq!(parts_asked_for(Some("2")), [false, true]);
+        assert_eq!(parts_asked_for(Some("3")), [true, true]);
+    }
+
+    struct Lines;
+
+    impl Solution for Lines {
+        type Input = Vec<String>;
+
+        fn parse(input: &str) -> Self::Input {
+            input.lines().map(String::from).collect()
+        }
+
+        fn part1(lines: &Self::Input) -> Answer {
+            lines.len().into()
+        }
+
+        fn part2(lines: &Self::Input) -> Answer {
+            lines.first().map(String::as_str).into()
+        }
+    }
+
+    #[test]
+    fn test_bench() {
+        let timings = bench::<Lines>("a\nb\n", [false, true], 3);
+
+        assert_eq!(
+            timings.iter().map(|(step, _)| *step).collect::<Vec<_>>(),
+            ["parse", "part2"]
+        );
+        assert!(timings.iter().all(|(_, samples)| samples.len() == 3));
+    }
+
+    #[test]
+    fn test_json() {
+        let json = json::<Lines>("a\nb\n", [true, false]);
+
+        assert!(json.starts_with("{\"part1\":\"2\",\"part2\":null,\"timings_ms\":{\"parse\":"));
+        assert!(json.contains(",\"part1\":"));
+        assert_eq!(json.matches("\"part2\"").count(), 1);
+        assert!(json.ends_with("}}"));
+    }
+
+    #[test]
+    fn test_json_string() {
+        assert_eq!(json_string("4,3,0"), "\"4,3,0\"");
+        assert_eq!(json_string("say \"hi\"\\\n"), r#""say \"hi\"\\\n""#);
+        assert_eq!(json_string("\u{1}"), r#""\u0001""#);
+    }
+
+    #[test]
+    fn test_solution() {
+        let input = Lines::parse("first\nsecond\n");
+
+        assert_eq!(Lines::part1(&input), Answer::Number(2));
+        assert_eq!(Lines::part2(&input), Answer::from("first"));
+    }
+}
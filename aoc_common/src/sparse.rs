@@ -0,0 +1,423 @@
+//! Maps with no edges, for puzzles where things wander off in any direction
+//! and only the cells something is in are worth keeping.
+
+use std::collections::hash_map::{self, HashMap};
+use std::hash::Hash;
+
+use crate::grid::Grid;
+use crate::point::{Point2, Point3};
+
+/// The points a `SparseGrid` can be keyed by
+pub trait SparsePoint: Copy + Eq + Hash {
+    /// The smallest of each coordinate
+    fn lower(self, other: Self) -> Self;
+    /// The largest of each coordinate
+    fn upper(self, other: Self) -> Self;
+    /// Whether the points share any coordinate, so one is on the edge of a
+    /// box with the other in its corner
+    fn shares_coordinate(self, other: Self) -> bool;
+}
+
+impl SparsePoint for Point2 {
+    fn lower(self, other: Self) -> Self {
+        Point2::new(self.x.min(other.x), self.y.min(other.y))
+    }
+
+    fn upper(self, other: Self) -> Self {
+        Point2::new(self.x.max(other.x), self.y.max(other.y))
+    }
+
+    fn shares_coordinate(self, other: Self) -> bool {
+        self.x == other.x || self.y == other.y
+    }
+}
+
+impl SparsePoint for Point3 {
+    fn lower(self, other: Self) -> Self {
+        Point3::new(
+            self.x.min(other.x),
+            self.y.min(other.y),
+            self.z.min(other.z),
+        )
+    }
+
+    fn upper(self, other: Self) -> Self {
+        Point3::new(
+            self.x.max(other.x),
+            self.y.max(other.y),
+            self.z.max(other.z),
+        )
+    }
+
+    fn shares_coordinate(self, other: Self) -> bool {
+        self.x == other.x || self.y == other.y || self.z == other.z
+    }
+}
+
+/// The smallest box holding every occupied cell, `min` and `max` included
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Bounds<P> {
+    pub min: P,
+    pub max: P,
+}
+
+impl<P: SparsePoint> Bounds<P> {
+    fn of(point: P) -> Self {
+        Bounds {
+            min: point,
+            max: point,
+        }
+    }
+
+    fn grow(self, point: P) -> Self {
+        Bounds {
+            min: self.min.lower(point),
+            max: self.max.upper(point),
+        }
+    }
+
+    pub fn contains(&self, point: P) -> bool {
+        self.min.lower(point) == self.min && self.max.upper(point) == self.max
+    }
+}
+
+impl Bounds<Point2> {
+    pub fn width(&self) -> usize {
+        self.min.x.abs_diff(self.max.x) + 1
+    }
+
+    pub fn height(&self) -> usize {
+        self.min.y.abs_diff(self.max.y) + 1
+    }
+
+    /// Every point in the box, row by row from the top left
+    pub fn points(&self) -> impl Iterator<Item = Point2> {
+        let Bounds { min, max } = *self;
+        (min.y..=max.y).flat_map(move |y| (min.x..=max.x).map(move |x| Point2::new(x, y)))
+    }
+}
+
+impl Bounds<Point3> {
+    pub fn width(&self) -> usize {
+        self.min.x.abs_diff(self.max.x) + 1
+    }
+
+    pub fn height(&self) -> usize {
+        self.min.y.abs_diff(self.max.y) + 1
+    }
+
+    pub fn depth(&self) -> usize {
+        self.min.z.abs_diff(self.max.z) + 1
+    }
+}
+
+/// Cells by position with no edges, `SparseGrid<T>` on a 2D map and
+/// `SparseGrid<T, Point3>` in space. It keeps its bounding box up to date as
+/// cells come and go.
+#[derive(Debug, Clone)]
+pub struct SparseGrid<T, P = Point2> {
+    cells: HashMap<P, T>,
+    bounds: Option<Bounds<P>>,
+}
+
+impl<T, P: SparsePoint> Default for SparseGrid<T, P> {
+    fn default() -> Self {
+        SparseGrid {
+            cells: HashMap::new(),
+            bounds: None,
+        }
+    }
+}
+
+/// Grids are equal when the same cells hold the same values
+impl<T: PartialEq, P: SparsePoint> PartialEq for SparseGrid<T, P> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cells == other.cells
+    }
+}
+
+impl<T: Eq, P: SparsePoint> Eq for SparseGrid<T, P> {}
+
+impl<T, P: SparsePoint> SparseGrid<T, P> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Puts the value in the cell, giving back what was there
+    pub fn insert(&mut self, point: impl Into<P>, value: T) -> Option<T> {
+        let point = point.into();
+        self.bounds = Some(match self.bounds {
+            Some(bounds) => bounds.grow(point),
+            None => Bounds::of(point),
+        });
+        self.cells.insert(point, value)
+    }
+
+    /// Empties the cell, shrinking the bounding box if it was on the edge
+    pub fn remove(&mut self, point: impl Into<P>) -> Option<T> {
+        let point = point.into();
+        let removed = self.cells.remove(&point)?;
+
+        if let Some(bounds) = self.bounds {
+            if point.shares_coordinate(bounds.min) || point.shares_coordinate(bounds.max) {
+                self.bounds = self.measure();
+            }
+        }
+
+        Some(removed)
+    }
+
+    fn measure(&self) -> Option<Bounds<P>> {
+        let mut points = self.cells.keys();
+        let first = Bounds::of(*points.next()?);
+        Some(points.fold(first, |bounds, &point| bounds.grow(point)))
+    }
+
+    pub fn get(&self, point: impl Into<P>) -> Option<&T> {
+        self.cells.get(&point.into())
+    }
+
+    /// Changing a cell in place leaves the bounding box alone, as only
+    /// `insert` and `remove` can move its edges
+    pub fn get_mut(&mut self, point: impl Into<P>) -> Option<&mut T> {
+        self.cells.get_mut(&point.into())
+    }
+
+    pub fn contains(&self, point: impl Into<P>) -> bool {
+        self.cells.contains_key(&point.into())
+    }
+
+    /// How many cells are occupied
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// `None` while nothing is in the grid
+    pub fn bounds(&self) -> Option<Bounds<P>> {
+        self.bounds
+    }
+
+    /// The occupied cells, in no particular order
+    pub fn iter(&self) -> impl Iterator<Item = (P, &T)> {
+        self.cells.iter().map(|(&point, value)| (point, value))
+    }
+
+    /// The occupied points, in no particular order
+    pub fn points(&self) -> impl Iterator<Item = P> + '_ {
+        self.cells.keys().copied()
+    }
+
+    /// A grid of the same points with every value converted
+    pub fn map<U>(&self, mut convert: impl FnMut(&T) -> U) -> SparseGrid<U, P> {
+        SparseGrid {
+            cells: self
+                .cells
+                .iter()
+                .map(|(&point, value)| (point, convert(value)))
+                .collect(),
+            bounds: self.bounds,
+        }
+    }
+}
+
+impl<T> SparseGrid<T> {
+    /// Draws the bounding box one character per cell, `draw` getting `None`
+    /// for the empty ones, with a newline after every row
+    pub fn render(&self, draw: impl FnMut(Option<&T>) -> char) -> String {
+        match self.bounds {
+            Some(bounds) => self.render_within(bounds, draw),
+            None => String::new(),
+        }
+    }
+
+    /// Draws any box, for when the picture has to include empty edges
+    pub fn render_within(
+        &self,
+        bounds: Bounds<Point2>,
+        mut draw: impl FnMut(Option<&T>) -> char,
+    ) -> String {
+        let mut out = String::with_capacity((bounds.width() + 1) * bounds.height());
+
+        for point in bounds.points() {
+            out.push(draw(self.cells.get(&point)));
+            if point.x == bounds.max.x {
+                out.push('\n');
+            }
+        }
+
+        out
+    }
+
+    /// Keeps the cells of the dense grid `keep` picks out, at the same
+    /// positions
+    pub fn from_grid(grid: &Grid<T>, mut keep: impl FnMut(&T) -> bool) -> Self
+    where
+        T: Clone,
+    {
+        grid.iter()
+            .filter(|(_, value)| keep(value))
+            .map(|(pos, value)| (Point2::from(pos), value.clone()))
+            .collect()
+    }
+
+    /// A dense grid of the bounding box, so its top left is `bounds().min`,
+    /// with `empty` in the unoccupied cells. `None` while nothing is in the
+    /// grid.
+    pub fn to_grid(&self, empty: T) -> Option<Grid<T>>
+    where
+        T: Clone,
+    {
+        let bounds = self.bounds?;
+        let mut grid = Grid::new(bounds.width(), bounds.height(), empty);
+
+        for (point, value) in self.iter() {
+            grid[point - bounds.min] = value.clone();
+        }
+
+        Some(grid)
+    }
+}
+
+impl<T> SparseGrid<T, Point3> {
+    /// The 2D slice of cells at height `z`
+    pub fn layer(&self, z: isize) -> SparseGrid<T>
+    where
+        T: Clone,
+    {
+        self.iter()
+            .filter(|(point, _)| point.z == z)
+            .map(|(point, value)| (Point2::new(point.x, point.y), value.clone()))
+            .collect()
+    }
+}
+
+impl<T, P: SparsePoint, Q: Into<P>> FromIterator<(Q, T)> for SparseGrid<T, P> {
+    fn from_iter<I: IntoIterator<Item = (Q, T)>>(iter: I) -> Self {
+        let mut grid = SparseGrid::new();
+        grid.extend(iter);
+        grid
+    }
+}
+
+impl<T, P: SparsePoint, Q: Into<P>> Extend<(Q, T)> for SparseGrid<T, P> {
+    fn extend<I: IntoIterator<Item = (Q, T)>>(&mut self, iter: I) {
+        for (point, value) in iter {
+            self.insert(point, value);
+        }
+    }
+}
+
+impl<'a, T, P: SparsePoint> IntoIterator for &'a SparseGrid<T, P> {
+    type Item = (&'a P, &'a T);
+    type IntoIter = hash_map::Iter<'a, P, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.cells.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bounds_follow_cells() {
+        let mut grid = SparseGrid::new();
+        assert_eq!(grid.bounds(), None);
+
+        grid.insert((0, 0), 'a');
+        grid.insert((-3, 2), 'b');
+        grid.insert((5, -1), 'c');
+        assert_eq!(
+            grid.bounds(),
+            Some(Bounds {
+                min: Point2::new(-3, -1),
+                max: Point2::new(5, 2)
+            })
+        );
+        assert_eq!(grid.len(), 3);
+
+        assert_eq!(grid.remove((5, -1)), Some('c'));
+        assert_eq!(grid.remove((5, -1)), None);
+        assert_eq!(
+            grid.bounds(),
+            Some(Bounds {
+                min: Point2::new(-3, 0),
+                max: Point2::new(0, 2)
+            })
+        );
+
+        grid.remove((0, 0));
+        grid.remove((-3, 2));
+        assert_eq!(grid.bounds(), None);
+        assert!(grid.is_empty());
+    }
+
+    #[test]
+    fn test_render() {
+        let grid: SparseGrid<char> = [((-1, -1), '#'), ((1, 0), 'o')].into_iter().collect();
+
+        assert_eq!(
+            grid.render(|cell| cell.copied().unwrap_or('.')),
+            "#..\n..o\n"
+        );
+
+        let bounds = Bounds {
+            min: Point2::new(-1, -1),
+            max: Point2::new(2, 1),
+        };
+        assert_eq!(
+            grid.render_within(bounds, |cell| cell.map_or('.', |_| '#')),
+            "#...\n..#.\n....\n"
+        );
+        assert_eq!(SparseGrid::<char>::new().render(|_| '#'), "");
+    }
+
+    #[test]
+    fn test_grid_round_trip() {
+        let dense = Grid::parse(&["..#", "#..", "..."]).unwrap();
+        let sparse = SparseGrid::from_grid(&dense, |&c| c == '#');
+
+        assert_eq!(sparse.len(), 2);
+        assert!(sparse.contains((2, 0)));
+        assert_eq!(sparse.get((0, 1)), Some(&'#'));
+
+        // The empty last row is outside the bounding box
+        let back = sparse.to_grid('.').unwrap();
+        assert_eq!(back, Grid::parse(&["..#", "#.."]).unwrap());
+        assert_eq!(SparseGrid::<char>::new().to_grid('.'), None);
+    }
+
+    #[test]
+    fn test_3d() {
+        let mut space: SparseGrid<(), Point3> = [(0, 0, 0), (1, 0, 0), (0, 3, -2)]
+            .into_iter()
+            .map(|p| (p, ()))
+            .collect();
+        space.insert(Point3::new(1, 1, 0), ());
+
+        let bounds = space.bounds().unwrap();
+        assert_eq!(bounds.min, Point3::new(0, 0, -2));
+        assert_eq!(bounds.max, Point3::new(1, 3, 0));
+        assert_eq!((bounds.width(), bounds.height(), bounds.depth()), (2, 4, 3));
+        assert!(bounds.contains(Point3::new(1, 2, -1)));
+        assert!(!bounds.contains(Point3::new(1, 2, 1)));
+
+        // Exposed faces, like lava droplets
+        let faces = space
+            .points()
+            .flat_map(Point3::neighbours6)
+            .filter(|&p| !space.contains(p))
+            .count();
+        assert_eq!(faces, 4 * 6 - 2 * 2);
+
+        assert_eq!(
+            space.layer(0).render(|c| c.map_or('.', |_| '#')),
+            "##\n.#\n"
+        );
+    }
+}
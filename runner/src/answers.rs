@@ -0,0 +1,160 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+use serde::Deserialize;
+
+use crate::error::RunError;
+
+/// Each year keeps its confirmed answers in this file, next to the days
+pub const FILE_NAME: &str = "answers.toml";
+
+/// An answer as written in answers.toml, where plain numbers can be left
+/// unquoted. Only numbers that fit an `i64` can be, anything bigger has to be
+/// written as a string.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(untagged)]
+pub enum Recorded {
+    Number(i64),
+    Text(String),
+}
+
+impl fmt::Display for Recorded {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Recorded::Number(n) => write!(f, "{n}"),
+            Recorded::Text(text) => write!(f, "{text}"),
+        }
+    }
+}
+
+/// What is known about one day's answers
+///
+/// ```toml
+/// [day_21]
+/// part1_wrong = [225994]
+/// part1_too_high = [235218, 226566]
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DayAnswers {
+    pub part1: Option<Recorded>,
+    pub part2: Option<Recorded>,
+    /// Answers that were submitted and turned out wrong
+    #[serde(default)]
+    pub part1_wrong: Vec<Recorded>,
+    #[serde(default)]
+    pub part2_wrong: Vec<Recorded>,
+    /// Wrong answers that were too high, so anything as high is wrong too
+    #[serde(default)]
+    pub part1_too_high: Vec<i64>,
+    #[serde(default)]
+    pub part2_too_high: Vec<i64>,
+    /// Wrong answers that were too low, so anything as low is wrong too
+    #[serde(default)]
+    pub part1_too_low: Vec<i64>,
+    #[serde(default)]
+    pub part2_too_low: Vec<i64>,
+}
+
+impl DayAnswers {
+    pub fn answer(&self, part: u8) -> Option<String> {
+        match part {
+            1 => self.part1.as_ref(),
+            _ => self.part2.as_ref(),
+        }
+        .map(Recorded::to_string)
+    }
+
+    pub fn wrong(&self, part: u8) -> &[Recorded] {
+        match part {
+            1 => &self.part1_wrong,
+            _ => &self.part2_wrong,
+        }
+    }
+
+    /// Why the answer is known to be wrong, if it is
+    pub fn known_wrong(&self, part: u8, answer: &str) -> Option<&'static str> {
+        if self.wrong(part).iter().any(|w| w.to_string() == answer) {
+            return Some("is known to be wrong");
+        }
+
+        let answer: i64 = answer.parse().ok()?;
+        let (too_high, too_low) = match part {
+            1 => (&self.part1_too_high, &self.part1_too_low),
+            _ => (&self.part2_too_high, &self.part2_too_low),
+        };
+        if too_high.iter().any(|&high| answer >= high) {
+            return Some("is too high");
+        }
+        if too_low.iter().any(|&low| answer <= low) {
+            return Some("is too low");
+        }
+        None
+    }
+}
+
+/// The year's answers by day number, empty when the year has no answers.toml
+pub fn load(year_dir: &Path) -> Result<BTreeMap<u8, DayAnswers>, RunError> {
+    let path = year_dir.join(FILE_NAME);
+    let text = match fs::read_to_string(&path) {
+        Ok(text) => text,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(BTreeMap::new()),
+        Err(e) => return Err(RunError::io(&path, e)),
+    };
+
+    parse(&text).map_err(|message| RunError::Answers { path, message })
+}
+
+fn parse(text: &str) -> Result<BTreeMap<u8, DayAnswers>, String> {
+    let days: BTreeMap<String, DayAnswers> = toml::from_str(text).map_err(|e| e.to_string())?;
+
+    days.into_iter()
+        .map(|(name, answers)| {
+            name.strip_prefix("day_")
+                .and_then(|n| n.parse::<u8>().ok())
+                .map(|day| (day, answers))
+                .ok_or_else(|| format!("[{name}] should be a day like [day_07]"))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let days = parse(
+            "[day_01]\npart1 = 138\npart2 = \"1771\"\n\n[day_17]\npart1_wrong = [\"4,3,0\", 12]\n",
+        )
+        .unwrap();
+
+        assert_eq!(days[&1].answer(1), Some("138".to_string()));
+        assert_eq!(days[&1].answer(2), Some("1771".to_string()));
+        assert_eq!(days[&17].answer(1), None);
+        assert!(days[&17].known_wrong(1, "4,3,0").is_some());
+        assert!(days[&17].known_wrong(1, "12").is_some());
+        assert_eq!(days[&17].known_wrong(2, "12"), None);
+    }
+
+    #[test]
+    fn test_too_high_and_low() {
+        let days =
+            parse("[day_06]\npart2_too_high = [1567, 1600]\npart2_too_low = [1500]\n").unwrap();
+
+        assert_eq!(days[&6].known_wrong(2, "1567"), Some("is too high"));
+        assert_eq!(days[&6].known_wrong(2, "1570"), Some("is too high"));
+        assert_eq!(days[&6].known_wrong(2, "1500"), Some("is too low"));
+        assert_eq!(days[&6].known_wrong(2, "1562"), None);
+        assert_eq!(days[&6].known_wrong(2, "(28,56)"), None);
+        assert_eq!(days[&6].known_wrong(1, "1570"), None);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(parse("[day_01]\npart3 = 1\n").is_err());
+        assert!(parse("[first]\npart1 = 1\n").is_err());
+    }
+}
@@ -0,0 +1,312 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use aoc_common::solution::{FORMAT_VAR, ITERATIONS_VAR};
+use serde::{Deserialize, Serialize};
+
+use crate::day::{self, Day};
+use crate::error::RunError;
+use crate::run;
+
+/// The fastest, middle and slowest of a step's runs
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Stats {
+    pub min_ms: f64,
+    pub median_ms: f64,
+    pub max_ms: f64,
+}
+
+impl Stats {
+    pub fn of(samples: &[Duration]) -> Option<Self> {
+        let mut ms = samples
+            .iter()
+            .map(|d| d.as_secs_f64() * 1000.0)
+            .collect::<Vec<_>>();
+        ms.sort_by(f64::total_cmp);
+
+        Some(Stats {
+            min_ms: *ms.first()?,
+            median_ms: median(&ms),
+            max_ms: *ms.last()?,
+        })
+    }
+}
+
+fn median(sorted: &[f64]) -> f64 {
+    let mid = sorted.len() / 2;
+    match sorted.len() % 2 {
+        0 => (sorted[mid - 1] + sorted[mid]) / 2.0,
+        _ => sorted[mid],
+    }
+}
+
+/// How long a day took. Only `Solution` days can time their steps, older days
+/// are timed as a whole.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Timings {
+    pub parse: Option<Stats>,
+    pub part1: Option<Stats>,
+    pub part2: Option<Stats>,
+    pub total: Stats,
+}
+
+/// Timings keyed by `YEAR/day_NN`, the shape of a saved baseline
+pub type Results = BTreeMap<String, Timings>;
+
+pub struct Bench<'a> {
+    pub iterations: usize,
+    /// How long one run of a day can take
+    pub timeout: Duration,
+    /// Earlier results to compare against
+    pub baseline: Option<&'a Path>,
+    /// How much slower than the baseline, in percent, counts as a regression
+    pub threshold: f64,
+    /// Where to save these results for next time
+    pub save: Option<&'a Path>,
+}
+
+/// Times every day with an input in the years, printing a table for each year
+/// with the slowest day first
+pub fn bench(root: &Path, years: &[u16], options: &Bench) -> Result<(), RunError> {
+    let baseline = options.baseline.map(load).transpose()?;
+    let mut results = Results::new();
+
+    for &year in years {
+        let mut timed = Vec::new();
+        let mut failed = Vec::new();
+
+        for day in day::days(root, year)? {
+            if !day.has_input() {
+                continue;
+            }
+
+            match time(&day, options) {
+                Ok(timings) => timed.push((day, timings)),
+                Err(err) => failed.push(err),
+            }
+        }
+
+        timed.sort_by(|(_, a), (_, b)| b.total.median_ms.total_cmp(&a.total.median_ms));
+        print_table(year, &timed);
+        for err in failed {
+            println!("  {err}");
+        }
+
+        results.extend(timed.into_iter().map(|(day, t)| (key(&day), t)));
+    }
+
+    if let Some(path) = options.save {
+        let json = serde_json::to_string_pretty(&results).expect("Timings serialise");
+        fs::write(path, json + "\n").map_err(|e| RunError::io(path, e))?;
+        println!("Saved the timings to {}", path.display());
+    }
+
+    let regressions = match &baseline {
+        Some(baseline) => compare(&results, baseline, options.threshold),
+        None => Vec::new(),
+    };
+    for regression in &regressions {
+        println!("{regression}");
+    }
+
+    match regressions.len() {
+        0 => Ok(()),
+        count => Err(RunError::Regressed(count)),
+    }
+}
+
+/// Builds the day and times it, `Solution` days time each step themselves
+pub fn time(day: &Day, options: &Bench) -> Result<Timings, RunError> {
+    let binary = run::build(day)?;
+
+    if !day.is_solution() {
+        let mut samples = Vec::new();
+        for _ in 0..options.iterations {
+            let started = Instant::now();
+            run::capture(day, &binary, &run::Run::default(), &[], options.timeout)?;
+            samples.push(started.elapsed());
+        }
+
+        return Ok(Timings {
+            parse: None,
+            part1: None,
+            part2: None,
+            total: Stats::of(&samples).expect("At least one iteration"),
+        });
+    }
+
+    let iterations = options.iterations.to_string();
+    let text = run::capture(
+        day,
+        &binary,
+        &run::Run::default(),
+        &[(FORMAT_VAR, "bench"), (ITERATIONS_VAR, &iterations)],
+        options.timeout * options.iterations as u32,
+    )?;
+
+    let steps = parse_steps(&text);
+    let step = |name: &str| steps.get(name).and_then(|s| Stats::of(s));
+
+    let totals = (0..options.iterations)
+        .map(|i| steps.values().filter_map(|s| s.get(i)).sum())
+        .collect::<Vec<Duration>>();
+
+    Ok(Timings {
+        parse: step("parse"),
+        part1: step("part1"),
+        part2: step("part2"),
+        total: Stats::of(&totals).expect("At least one iteration"),
+    })
+}
+
+/// Reads the `step nanos nanos ...` lines a `Solution` day prints when
+/// benchmarking
+fn parse_steps(text: &str) -> BTreeMap<String, Vec<Duration>> {
+    text.lines()
+        .filter_map(|line| {
+            let mut words = line.split_whitespace();
+            let step = words.next()?.to_string();
+            let samples = words
+                .filter_map(|n| n.parse().ok())
+                .map(Duration::from_nanos)
+                .collect();
+            Some((step, samples))
+        })
+        .collect()
+}
+
+fn key(day: &Day) -> String {
+    format!("{}/day_{:02}", day.year, day.day)
+}
+
+fn load(path: &Path) -> Result<Results, RunError> {
+    let text = fs::read_to_string(path).map_err(|e| RunError::io(path, e))?;
+    serde_json::from_str(&text).map_err(|e| RunError::Baseline {
+        path: path.to_path_buf(),
+        message: e.to_string(),
+    })
+}
+
+/// Days whose median got slower than the baseline's by more than the
+/// threshold
+fn compare(results: &Results, baseline: &Results, threshold: f64) -> Vec<String> {
+    results
+        .iter()
+        .filter_map(|(key, timings)| {
+            let was = baseline.get(key)?.total.median_ms;
+            let now = timings.total.median_ms;
+            let slower = (now / was - 1.0) * 100.0;
+
+            (slower > threshold).then(|| {
+                format!(
+                    "{key} is {slower:.0}% slower than the baseline, {} was {}",
+                    format_ms(now),
+                    format_ms(was)
+                )
+            })
+        })
+        .collect()
+}
+
+fn print_table(year: u16, timed: &[(Day, Timings)]) {
+    let headings = ["parse", "part 1", "part 2", "min", "median", "max"];
+    let row = |name: &str, cells: &[String]| {
+        let cells = cells.iter().map(|c| format!("{c:>10}")).collect::<String>();
+        println!("{name:<8}{cells}");
+    };
+
+    row(&year.to_string(), &headings.map(String::from));
+    for (day, timings) in timed {
+        let median =
+            |stats: Option<Stats>| stats.map_or("-".to_string(), |s| format_ms(s.median_ms));
+        row(
+            &format!("day {:02}", day.day),
+            &[
+                median(timings.parse),
+                median(timings.part1),
+                median(timings.part2),
+                format_ms(timings.total.min_ms),
+                format_ms(timings.total.median_ms),
+                format_ms(timings.total.max_ms),
+            ],
+        );
+    }
+}
+
+/// Milliseconds in whichever unit reads best
+fn format_ms(ms: f64) -> String {
+    match ms {
+        ms if ms >= 1000.0 => format!("{:.2}s", ms / 1000.0),
+        ms if ms >= 1.0 => format!("{ms:.2}ms"),
+        ms if ms >= 0.001 => format!("{:.1}µs", ms * 1000.0),
+        ms => format!("{:.0}ns", ms * 1_000_000.0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stats() {
+        let samples = [5, 1, 3, 2].map(Duration::from_millis);
+        let stats = Stats::of(&samples).unwrap();
+
+        assert_eq!(
+            stats,
+            Stats {
+                min_ms: 1.0,
+                median_ms: 2.5,
+                max_ms: 5.0
+            }
+        );
+        assert_eq!(Stats::of(&[]), None);
+    }
+
+    #[test]
+    fn test_parse_steps() {
+        let steps = parse_steps("parse 10 30\npart1 2000 1000\n");
+
+        assert_eq!(steps["parse"], [10, 30].map(Duration::from_nanos));
+        assert_eq!(steps["part1"], [2000, 1000].map(Duration::from_nanos));
+        assert!(!steps.contains_key("part2"));
+    }
+
+    #[test]
+    fn test_format_ms() {
+        assert_eq!(format_ms(2500.0), "2.50s");
+        assert_eq!(format_ms(12.345), "12.35ms");
+        assert_eq!(format_ms(0.0123), "12.3µs");
+        assert_eq!(format_ms(0.00025), "250ns");
+    }
+
+    #[test]
+    fn test_compare() {
+        let timings = |median_ms| Timings {
+            parse: None,
+            part1: None,
+            part2: None,
+            total: Stats {
+                min_ms: median_ms,
+                median_ms,
+                max_ms: median_ms,
+            },
+        };
+        let baseline = Results::from([
+            ("2030/day_01".to_string(), timings(100.0)),
+            ("2030/day_02".to_string(), timings(100.0)),
+        ]);
+        let results = Results::from([
+            ("2030/day_01".to_string(), timings(110.0)),
+            ("2030/day_02".to_string(), timings(150.0)),
+            ("2030/day_03".to_string(), timings(900.0)),
+        ]);
+
+        assert_eq!(
+            compare(&results, &baseline, 20.0),
+            ["2030/day_02 is 50% slower than the baseline, 150.00ms was 100.00ms"]
+        );
+    }
+}
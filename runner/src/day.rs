@@ -0,0 +1,152 @@
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::error::RunError;
+
+/// A day's crate inside a year folder
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Day {
+    pub year: u16,
+    pub day: u8,
+    pub dir: PathBuf,
+}
+
+impl Day {
+    pub fn manifest(&self) -> PathBuf {
+        self.dir.join("Cargo.toml")
+    }
+
+    /// Where the day's own puzzle input lives
+    pub fn input(&self) -> PathBuf {
+        self.dir.join("input.txt")
+    }
+
+    pub fn has_input(&self) -> bool {
+        fs::read_to_string(self.input()).is_ok_and(|text| !text.trim().is_empty())
+    }
+
+    /// Days built on `aoc_common::Solution` say so in their Cargo.toml
+    ///
+    /// ```toml
+    /// [package.metadata.aoc]
+    /// solution = true
+    /// ```
+    ///
+    /// and can print bare answers, older days only print sentences
+    pub fn is_solution(&self) -> bool {
+        fs::read_to_string(self.manifest())
+            .ok()
+            .and_then(|text| text.parse::<toml::Table>().ok())
+            .and_then(|manifest| {
+                manifest
+                    .get("package")?
+                    .get("metadata")?
+                    .get("aoc")?
+                    .get("solution")?
+                    .as_bool()
+            })
+            .unwrap_or(false)
+    }
+}
+
+impl fmt::Display for Day {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} day {:02}", self.year, self.day)
+    }
+}
+
+/// The first year there was an Advent of Code
+const FIRST_YEAR: u16 = 2015;
+
+/// Every year folder under the root, in order
+pub fn years(root: &Path) -> Result<Vec<u16>, RunError> {
+    let entries = fs::read_dir(root).map_err(|e| RunError::io(root, e))?;
+
+    let mut years = Vec::new();
+    for entry in entries {
+        let path = entry.map_err(|e| RunError::io(root, e))?.path();
+        let year = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .and_then(|n| n.parse::<u16>().ok());
+        if let Some(year) = year.filter(|&y| y >= FIRST_YEAR && path.is_dir()) {
+            years.push(year);
+        }
+    }
+    years.sort();
+
+    Ok(years)
+}
+
+/// Every day in the year, in order
+pub fn days(root: &Path, year: u16) -> Result<Vec<Day>, RunError> {
+    let year_dir = root.join(year.to_string());
+    if !year_dir.is_dir() {
+        return Err(RunError::YearMissing(year_dir));
+    }
+
+    let entries = fs::read_dir(&year_dir).map_err(|e| RunError::io(&year_dir, e))?;
+
+    let mut days = Vec::new();
+    for entry in entries {
+        let dir = entry.map_err(|e| RunError::io(&year_dir, e))?.path();
+        if let Some(day) = day_number(&dir).filter(|_| dir.join("Cargo.toml").is_file()) {
+            days.push(Day { year, day, dir });
+        }
+    }
+    days.sort();
+
+    Ok(days)
+}
+
+/// Finds the one day, whether its folder is `day_03` or `day_3`
+pub fn find(root: &Path, year: u16, day: u8) -> Result<Day, RunError> {
+    days(root, year)?
+        .into_iter()
+        .find(|d| d.day == day)
+        .ok_or(RunError::DayMissing { year, day })
+}
+
+fn day_number(path: &Path) -> Option<u8> {
+    path.file_name()?
+        .to_str()?
+        .strip_prefix("day_")?
+        .parse()
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_day_number() {
+        assert_eq!(day_number(Path::new("2024/day_07")), Some(7));
+        assert_eq!(day_number(Path::new("2024/day_7")), Some(7));
+        assert_eq!(day_number(Path::new("2024/day_07b")), None);
+        assert_eq!(day_number(Path::new("2024/target")), None);
+    }
+
+    #[test]
+    fn test_is_solution() -> Result<(), Box<dyn std::error::Error>> {
+        let dir = tempfile::tempdir()?;
+        let day = Day {
+            year: 2030,
+            day: 1,
+            dir: dir.path().to_path_buf(),
+        };
+        assert!(!day.is_solution());
+
+        fs::write(day.manifest(), "[package]\nname = \"day_01\"\n")?;
+        assert!(!day.is_solution());
+
+        fs::write(
+            day.manifest(),
+            "[package]\nname = \"day_01\"\n\n[package.metadata.aoc]\nsolution = true\n",
+        )?;
+        assert!(day.is_solution());
+
+        Ok(())
+    }
+}
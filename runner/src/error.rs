@@ -0,0 +1,119 @@
+use std::fmt;
+use std::io;
+use std::path::PathBuf;
+use std::process::{ExitCode, ExitStatus};
+use std::time::Duration;
+
+#[derive(Debug)]
+pub enum RunError {
+    /// There is no folder for the year under the root
+    YearMissing(PathBuf),
+    /// The year has no folder for the day
+    DayMissing { year: u16, day: u8 },
+    /// The input to feed the day could not be opened
+    Input { path: PathBuf, source: io::Error },
+    /// Cargo could not be started
+    Cargo(io::Error),
+    /// A day did not build, or exited with an error
+    Failed { name: String, status: ExitStatus },
+    /// A day built without a binary to run
+    NoBinary(String),
+    /// Some of the days run with `--all` failed
+    SomeFailed(Vec<String>),
+    /// A day was still going when its time ran out
+    TimedOut { name: String, timeout: Duration },
+    /// The year's answers.toml could not be read
+    Answers { path: PathBuf, message: String },
+    /// A day's JSON report could not be read
+    Report { name: String, message: String },
+    /// Some answers did not match the recorded ones
+    Mismatch(usize),
+    /// The timings to compare against could not be read
+    Baseline { path: PathBuf, message: String },
+    /// Some days got slower than the baseline
+    Regressed(usize),
+    /// Anything else that went wrong touching the disk
+    Io { path: PathBuf, source: io::Error },
+}
+
+impl RunError {
+    pub fn io(path: impl Into<PathBuf>, source: io::Error) -> Self {
+        RunError::Io {
+            path: path.into(),
+            source,
+        }
+    }
+
+    /// Exit codes follow sysexits.h so scripts can tell the failures apart
+    pub fn exit_code(&self) -> ExitCode {
+        match self {
+            RunError::YearMissing(_) => ExitCode::from(66),
+            RunError::DayMissing { .. } => ExitCode::from(66),
+            RunError::Input { .. } => ExitCode::from(66),
+            RunError::Cargo(_) => ExitCode::from(69),
+            RunError::Failed { .. } => ExitCode::from(70),
+            RunError::NoBinary(_) => ExitCode::from(70),
+            RunError::SomeFailed(_) => ExitCode::from(70),
+            RunError::TimedOut { .. } => ExitCode::from(75),
+            RunError::Answers { .. } => ExitCode::from(65),
+            RunError::Report { .. } => ExitCode::from(70),
+            RunError::Mismatch(_) => ExitCode::from(1),
+            RunError::Baseline { .. } => ExitCode::from(65),
+            RunError::Regressed(_) => ExitCode::from(1),
+            RunError::Io { .. } => ExitCode::from(74),
+        }
+    }
+}
+
+impl fmt::Display for RunError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RunError::YearMissing(path) => write!(f, "{} does not exist", path.display()),
+            RunError::DayMissing { year, day } => write!(f, "{year} has no day {day}"),
+            RunError::Input { path, source } => {
+                write!(f, "unable to read input {}: {source}", path.display())
+            }
+            RunError::Cargo(source) => write!(f, "unable to run cargo: {source}"),
+            RunError::Failed { name, status } => write!(f, "{name} failed, {status}"),
+            RunError::NoBinary(name) => write!(f, "{name} has no binary to run"),
+            RunError::SomeFailed(names) => write!(f, "failed: {}", names.join(", ")),
+            RunError::TimedOut { name, timeout } => {
+                write!(f, "{name} took longer than {}s", timeout.as_secs_f32())
+            }
+            RunError::Answers { path, message } => {
+                write!(f, "unable to read {}: {message}", path.display())
+            }
+            RunError::Report { name, message } => {
+                write!(f, "{name} printed an unreadable report: {message}")
+            }
+            RunError::Mismatch(1) => write!(f, "1 answer did not match"),
+            RunError::Mismatch(count) => write!(f, "{count} answers did not match"),
+            RunError::Baseline { path, message } => {
+                write!(f, "unable to read {}: {message}", path.display())
+            }
+            RunError::Regressed(1) => write!(f, "1 day got slower"),
+            RunError::Regressed(count) => write!(f, "{count} days got slower"),
+            RunError::Io { path, source } => write!(f, "{}: {source}", path.display()),
+        }
+    }
+}
+
+impl std::error::Error for RunError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            RunError::YearMissing(_)
+            | RunError::DayMissing { .. }
+            | RunError::Failed { .. }
+            | RunError::NoBinary(_)
+            | RunError::SomeFailed(_)
+            | RunError::TimedOut { .. }
+            | RunError::Answers { .. }
+            | RunError::Report { .. }
+            | RunError::Mismatch(_)
+            | RunError::Baseline { .. }
+            | RunError::Regressed(_) => None,
+            RunError::Input { source, .. } | RunError::Io { source, .. } => Some(source),
+            RunError::Cargo(source) => Some(source),
+        }
+    }
+}
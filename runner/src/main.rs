@@ -0,0 +1,165 @@
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::time::Duration;
+
+use clap::{Parser, Subcommand};
+
+mod answers;
+mod bench;
+mod day;
+mod error;
+mod output;
+mod run;
+mod verify;
+
+use error::RunError;
+
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct Cli {
+    /// Folder holding the year folders
+    #[arg(short, long, global = true, env = "AOC_ROOT", default_value = ".")]
+    root: PathBuf,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Builds a day and runs it on its input
+    Run {
+        year: u16,
+
+        /// Day number, leave it out with --all
+        #[arg(required_unless_present = "all", conflicts_with = "all")]
+        day: Option<u8>,
+
+        /// Runs every day in the year that has an input
+        #[arg(short, long)]
+        all: bool,
+
+        /// Input to use instead of the day's input.txt
+        #[arg(short, long, conflicts_with = "all")]
+        input: Option<PathBuf>,
+
+        /// Only answer this part
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+
+        /// Prints what the day printed, or its answers and timings as JSON
+        #[arg(short, long, value_enum, default_value_t)]
+        format: run::Format,
+
+        /// Arguments for the day itself, after --
+        #[arg(last = true)]
+        args: Vec<String>,
+    },
+    /// Runs every day that has an input and checks its answers against the
+    /// year's answers.toml
+    Verify {
+        /// Years to check, every year when left out
+        years: Vec<u16>,
+
+        /// Seconds a day can take before it counts as failed
+        #[arg(short, long, default_value_t = 60)]
+        timeout: u64,
+    },
+    /// Times every day that has an input, slowest first
+    Bench {
+        /// Years to time, every year when left out
+        years: Vec<u16>,
+
+        /// How many times to run each day
+        #[arg(short = 'n', long, default_value_t = 10, value_parser = clap::value_parser!(u32).range(1..))]
+        iterations: u32,
+
+        /// Seconds one run of a day can take before it counts as failed
+        #[arg(short, long, default_value_t = 60)]
+        timeout: u64,
+
+        /// Timings saved earlier with --save to compare against
+        #[arg(short, long)]
+        baseline: Option<PathBuf>,
+
+        /// Percent slower than the baseline that counts as a regression
+        #[arg(long, default_value_t = 20.0)]
+        threshold: f64,
+
+        /// Saves the timings as JSON
+        #[arg(short, long)]
+        save: Option<PathBuf>,
+    },
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+
+    let result = match cli.command {
+        Command::Run {
+            year,
+            day,
+            all: _,
+            input,
+            part,
+            format,
+            args,
+        } => run_year(&cli.root, year, day, input, part, format, &args),
+        Command::Verify { years, timeout } => match years.is_empty() {
+            true => day::years(&cli.root),
+            false => Ok(years),
+        }
+        .and_then(|years| verify::verify(&cli.root, &years, Duration::from_secs(timeout))),
+        Command::Bench {
+            years,
+            iterations,
+            timeout,
+            baseline,
+            threshold,
+            save,
+        } => {
+            let options = bench::Bench {
+                iterations: iterations as usize,
+                timeout: Duration::from_secs(timeout),
+                baseline: baseline.as_deref(),
+                threshold,
+                save: save.as_deref(),
+            };
+            match years.is_empty() {
+                true => day::years(&cli.root),
+                false => Ok(years),
+            }
+            .and_then(|years| bench::bench(&cli.root, &years, &options))
+        }
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("Error: {err}");
+            err.exit_code()
+        }
+    }
+}
+
+fn run_year(
+    root: &Path,
+    year: u16,
+    day: Option<u8>,
+    input: Option<PathBuf>,
+    part: Option<u8>,
+    format: run::Format,
+    args: &[String],
+) -> Result<(), RunError> {
+    let options = run::Run {
+        input: input.as_deref(),
+        part,
+        args,
+        format,
+    };
+
+    match day {
+        Some(day) => run::run(&day::find(root, year, day)?, &options),
+        None => run::run_all(&day::days(root, year)?, &options),
+    }
+}
@@ -0,0 +1,124 @@
+//! Picking the answers out of the sentences older days print, so they can be
+//! reported and checked much like the bare answers `Solution` days give. This
+//! is a best guess from the wording, not something the days promise, and a
+//! day whose sentences can not be told apart gets no answers at all.
+
+/// The lines that read like an answer, in the order they were printed. They
+/// have at least three plain words and none of the `{` or `[` a debug print
+/// leaves, which rules out pictures, dumps and labelled values too.
+pub fn sentences(text: &str) -> Vec<&str> {
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.contains(['{', '[']))
+        .filter(|line| line.split_whitespace().filter(|t| is_word(t)).count() >= 3)
+        .collect()
+}
+
+fn is_word(token: &str) -> bool {
+    let word = token.trim_end_matches([',', '.', ':', '!', '?']);
+    !word.is_empty() && word.chars().all(|c| c.is_alphabetic() || c == '-')
+}
+
+/// The sentence for the part, the first for part 1 and the second for part 2.
+/// A day printing more than two can not be told apart, so gives none.
+pub fn sentence(text: &str, part: u8) -> Option<&str> {
+    let sentences = sentences(text);
+    match sentences.len() {
+        0..=2 => sentences.get(part as usize - 1).copied(),
+        _ => None,
+    }
+}
+
+/// The part's answer, from its sentence
+pub fn answer(text: &str, part: u8) -> Option<String> {
+    sentence(text, part).and_then(answer_in)
+}
+
+/// The answer a sentence gives. That is the `(x, y)` it ends with or else its
+/// last number, which skips a count like the 25 in "After 25 blinks, there
+/// are 183620 stones". A code in capitals after "spell out" wins over any
+/// number, and a sentence without numbers can give a code in capitals too.
+pub fn answer_in(sentence: &str) -> Option<String> {
+    let sentence = sentence.trim_end_matches(['.', '!']);
+
+    if let Some(inside) = sentence
+        .strip_suffix(')')
+        .and_then(|s| s.rfind('(').map(|start| &s[start + 1..]))
+    {
+        if inside.contains(|c: char| c.is_ascii_digit()) {
+            return Some(inside.split_whitespace().collect());
+        }
+    }
+
+    let tokens: Vec<&str> = sentence
+        .split_whitespace()
+        .map(|token| {
+            token
+                .trim_end_matches(|c: char| !c.is_alphanumeric())
+                .trim_start_matches(|c: char| !c.is_alphanumeric() && c != '-')
+        })
+        .collect();
+
+    let is_code = |token: &&&str| token.len() > 1 && token.chars().all(|c| c.is_ascii_uppercase());
+
+    let spelled = tokens
+        .iter()
+        .position(|token| matches!(*token, "spell" | "spells"))
+        .and_then(|start| tokens[start..].iter().find(is_code));
+
+    if let Some(code) = spelled {
+        return Some(code.to_string());
+    }
+
+    let number = tokens
+        .iter()
+        .rev()
+        .find(|token| token.contains(|c: char| c.is_ascii_digit()));
+    let code = || tokens.iter().rev().find(is_code);
+
+    number.or_else(code).map(|answer| answer.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_answer_in() {
+        let answer = |sentence| answer_in(sentence).unwrap();
+
+        assert_eq!(answer("Santa is on floor 138"), "138");
+        assert_eq!(answer("There are 278 antinodes in bounds."), "278");
+        assert_eq!(answer("After 25 blinks, there are 183620 stones"), "183620");
+        assert_eq!(answer("Number of flashes after 100 steps: 1637"), "1637");
+        assert_eq!(answer("The program will output 4,3,0,5"), "4,3,0,5");
+        assert_eq!(answer("The byte that prevents exit is (28, 56)"), "28,56");
+        assert_eq!(answer("The lowest floor is -3."), "-3");
+        assert_eq!(answer("There are 21409 steps to reach ZZZ."), "21409");
+        assert_eq!(answer("The top crates spell out CMZ."), "CMZ");
+        assert_eq!(
+            answer("The top crates using CrateMover 9001 spell out MCD."),
+            "MCD"
+        );
+        assert_eq!(answer_in("There is no winner"), None);
+    }
+
+    #[test]
+    fn test_sentences() {
+        let text = "Tiles [((50, 24), '[')]\nCube Cube { x: 12, y: 3 }\n\
+                    Target ((52, 24), '.')\n..##.####\n\n\
+                    Visible dots after first fold: 942\nSeq <A^A>\n";
+
+        assert_eq!(sentences(text), ["Visible dots after first fold: 942"]);
+        assert_eq!(answer(text, 1).as_deref(), Some("942"));
+        assert_eq!(answer(text, 2), None);
+    }
+
+    #[test]
+    fn test_too_many_sentences() {
+        let text = "Part one is 1\nPart two is 2\nPart three is 3\n";
+
+        assert_eq!(sentence(text, 1), None);
+        assert_eq!(sentence(text, 2), None);
+    }
+}
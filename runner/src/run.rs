@@ -0,0 +1,281 @@
+use std::collections::BTreeMap;
+use std::ffi::OsString;
+use std::fs::File;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use aoc_common::solution::{FORMAT_VAR, PART_VAR};
+use serde::{Deserialize, Serialize};
+
+use crate::day::Day;
+use crate::error::RunError;
+use crate::output;
+
+/// How `run` shows a day's answers
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum Format {
+    /// Whatever the day prints
+    #[default]
+    Text,
+    /// One JSON object per day, see `Report`
+    Json,
+}
+
+#[derive(Default)]
+pub struct Run<'a> {
+    /// Feeds this file instead of the day's `input.txt`
+    pub input: Option<&'a Path>,
+    pub part: Option<u8>,
+    /// Passed on to the day after its input, for days that take arguments
+    pub args: &'a [String],
+    pub format: Format,
+}
+
+impl Run<'_> {
+    fn input(&self, day: &Day) -> Result<File, RunError> {
+        let path = self
+            .input
+            .map(Path::to_path_buf)
+            .unwrap_or_else(|| day.input());
+        File::open(&path).map_err(|source| RunError::Input { path, source })
+    }
+}
+
+/// A day's answers in the same shape whatever the day prints. For older days
+/// the answers are picked out of their sentences, and what they printed is
+/// kept as `output` in case that went wrong.
+#[derive(Debug, Serialize)]
+pub struct Report {
+    pub year: u16,
+    pub day: u8,
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output: Option<String>,
+    /// Each step a `Solution` day timed, and `total` for the whole run
+    pub timings_ms: BTreeMap<String, f64>,
+}
+
+/// What a `Solution` day prints with `AOC_FORMAT=json`
+#[derive(Debug, Deserialize)]
+struct Reported {
+    part1: Option<String>,
+    part2: Option<String>,
+    timings_ms: BTreeMap<String, f64>,
+}
+
+/// Builds the day in release mode and runs it on its input, its answers go
+/// straight to stdout
+pub fn run(day: &Day, options: &Run) -> Result<(), RunError> {
+    if options.part.is_some() && !day.is_solution() {
+        eprintln!("{day} is not a Solution day, so it answers both parts whatever --part says");
+    }
+
+    if options.format == Format::Json {
+        let report = report(day, options)?;
+        println!(
+            "{}",
+            serde_json::to_string(&report).expect("Reports serialise")
+        );
+        return Ok(());
+    }
+
+    let input = options.input(day)?;
+
+    let mut command = Command::new(cargo());
+    command
+        .args(["run", "--release", "--quiet", "--manifest-path"])
+        .arg(day.manifest())
+        .arg("--")
+        .args(options.args)
+        .stdin(input);
+    if let Some(part) = options.part {
+        command.env(PART_VAR, part.to_string());
+    }
+
+    let status = command.status().map_err(RunError::Cargo)?;
+    if !status.success() {
+        return Err(RunError::Failed {
+            name: day.to_string(),
+            status,
+        });
+    }
+
+    Ok(())
+}
+
+/// Runs every day in the year that has an input, carrying on past the ones
+/// that fail
+pub fn run_all(days: &[Day], options: &Run) -> Result<(), RunError> {
+    let mut failed = Vec::new();
+
+    let text = options.format == Format::Text;
+
+    for day in days {
+        if !day.has_input() {
+            match text {
+                true => println!("== {day}: no input, skipped"),
+                false => eprintln!("{day}: no input, skipped"),
+            }
+            continue;
+        }
+
+        if text {
+            println!("== {day}");
+        }
+        if let Err(err) = run(day, options) {
+            eprintln!("Error: {err}");
+            failed.push(day.to_string());
+        }
+    }
+
+    match failed.is_empty() {
+        true => Ok(()),
+        false => Err(RunError::SomeFailed(failed)),
+    }
+}
+
+/// Builds the day in release mode, giving back the binary so it can be run
+/// without cargo in between
+pub fn build(day: &Day) -> Result<PathBuf, RunError> {
+    let output = Command::new(cargo())
+        .args(["build", "--release", "--quiet", "--message-format=json"])
+        .arg("--manifest-path")
+        .arg(day.manifest())
+        .stderr(Stdio::inherit())
+        .output()
+        .map_err(RunError::Cargo)?;
+    if !output.status.success() {
+        return Err(RunError::Failed {
+            name: day.to_string(),
+            status: output.status,
+        });
+    }
+
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| serde_json::from_str::<serde_json::Value>(line).ok())
+        .filter(|message| message["reason"] == "compiler-artifact")
+        .filter_map(|message| message["executable"].as_str().map(PathBuf::from))
+        .next_back()
+        .ok_or_else(|| RunError::NoBinary(day.to_string()))
+}
+
+/// Builds and runs the day once, reporting its answers and how long it took
+pub fn report(day: &Day, options: &Run) -> Result<Report, RunError> {
+    let binary = build(day)?;
+
+    let started = Instant::now();
+    let text = capture(
+        day,
+        &binary,
+        options,
+        &[(FORMAT_VAR, "json")],
+        Duration::MAX,
+    )?;
+    let total = started.elapsed().as_secs_f64() * 1000.0;
+
+    let mut report = Report {
+        year: day.year,
+        day: day.day,
+        part1: None,
+        part2: None,
+        output: None,
+        timings_ms: BTreeMap::new(),
+    };
+    match day.is_solution() {
+        true => {
+            let reported: Reported = serde_json::from_str(&text).map_err(|e| RunError::Report {
+                name: day.to_string(),
+                message: e.to_string(),
+            })?;
+            report.part1 = reported.part1;
+            report.part2 = reported.part2;
+            report.timings_ms = reported.timings_ms;
+        }
+        false => {
+            let asked = |part| options.part.is_none_or(|p| p == part);
+            report.part1 = output::answer(&text, 1).filter(|_| asked(1));
+            report.part2 = output::answer(&text, 2).filter(|_| asked(2));
+            report.output = Some(text);
+        }
+    }
+    report.timings_ms.insert("total".to_string(), total);
+
+    Ok(report)
+}
+
+/// Runs a built day with the given environment, stopping it if it takes too
+/// long, and gives back what it printed
+pub fn capture(
+    day: &Day,
+    binary: &Path,
+    options: &Run,
+    env: &[(&str, &str)],
+    timeout: Duration,
+) -> Result<String, RunError> {
+    let input = options.input(day)?;
+
+    let mut command = Command::new(binary);
+    command
+        .current_dir(&day.dir)
+        .args(options.args)
+        .envs(env.iter().copied());
+    if let Some(part) = options.part {
+        command.env(PART_VAR, part.to_string());
+    }
+
+    let mut child = command
+        .stdin(input)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| RunError::io(&day.dir, e))?;
+
+    // Read as it comes so a chatty day does not block on a full pipe
+    let mut stdout = child.stdout.take().expect("stdout is piped");
+    let reader = thread::spawn(move || {
+        let mut text = String::new();
+        stdout.read_to_string(&mut text).map(|_| text)
+    });
+
+    // Poll often at first so short runs are timed closely, and less once a day
+    // is clearly taking its time
+    let started = Instant::now();
+    let mut poll = Duration::from_micros(100);
+    let status = loop {
+        if let Some(status) = child.try_wait().map_err(|e| RunError::io(&day.dir, e))? {
+            break status;
+        }
+        if started.elapsed() > timeout {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(RunError::TimedOut {
+                name: day.to_string(),
+                timeout,
+            });
+        }
+        thread::sleep(poll);
+        poll = (poll * 2).min(Duration::from_millis(20));
+    };
+
+    if !status.success() {
+        return Err(RunError::Failed {
+            name: day.to_string(),
+            status,
+        });
+    }
+
+    reader
+        .join()
+        .expect("The reader does not panic")
+        .map_err(|e| RunError::io(&day.dir, e))
+}
+
+/// The cargo that built this, so a pinned toolchain is used for the days too
+fn cargo() -> OsString {
+    std::env::var_os("CARGO").unwrap_or_else(|| "cargo".into())
+}
@@ -0,0 +1,252 @@
+use std::path::Path;
+use std::time::Duration;
+
+use aoc_common::solution::FORMAT_VAR;
+
+use crate::answers::{self, DayAnswers};
+use crate::day::{self, Day};
+use crate::error::RunError;
+use crate::output;
+use crate::run;
+
+/// What a day printed, bare answers from `Solution` days or the sentences
+/// older days print
+enum Output {
+    Answers(Vec<String>),
+    Text(String),
+}
+
+impl Output {
+    /// The part's answer, as `Solution` days give it or as picked out of the
+    /// part's sentence
+    fn answer(&self, part: u8) -> Option<String> {
+        match self {
+            Output::Answers(answers) => answers.get(part as usize - 1).cloned(),
+            Output::Text(text) => output::answer(text, part),
+        }
+    }
+
+    /// Whether the day gave this answer for the part. For older days it has to
+    /// be in the part's sentence, not as part of a longer word or number, and
+    /// only when their sentences can not be told apart is anywhere enough.
+    fn has(&self, part: u8, answer: &str) -> bool {
+        let Output::Text(text) = self else {
+            return self.answer(part).as_deref() == Some(answer);
+        };
+
+        let sentences = output::sentences(text);
+        match sentences.len() {
+            1 | 2 => sentences.get(part as usize - 1).is_some_and(|sentence| {
+                has_word(sentence, answer) || output::answer_in(sentence).as_deref() == Some(answer)
+            }),
+            _ => has_word(text, answer),
+        }
+    }
+}
+
+/// Whether the text has the word, as long as it is not part of a longer word
+/// or number
+fn has_word(text: &str, word: &str) -> bool {
+    text.match_indices(word).any(|(ix, _)| {
+        let is_word = |c: char| c.is_alphanumeric();
+        !text[..ix].ends_with(is_word) && !text[ix + word.len()..].starts_with(is_word)
+    })
+}
+
+#[derive(Debug, Default)]
+struct Tally {
+    passed: usize,
+    failed: usize,
+    missing: usize,
+}
+
+/// Runs every day in the years that has an input and compares what it
+/// answers with the year's answers.toml, printing a line for each day
+pub fn verify(root: &Path, years: &[u16], timeout: Duration) -> Result<(), RunError> {
+    let mut total = Tally::default();
+
+    for &year in years {
+        let recorded = answers::load(&root.join(year.to_string()))?;
+        let mut tally = Tally::default();
+
+        for day in day::days(root, year)? {
+            if !day.has_input() {
+                continue;
+            }
+
+            let Some(answers) = recorded.get(&day.day) else {
+                println!("{day}: missing");
+                tally.missing += 2;
+                continue;
+            };
+
+            let output = match output(&day, timeout) {
+                Ok(output) => output,
+                Err(err) => {
+                    println!("{day}: {err}");
+                    tally.failed += 2;
+                    continue;
+                }
+            };
+
+            let checks = [1, 2]
+                .map(|part| format!("part {part} {}", check(&output, answers, part, &mut tally)));
+            println!("{day}: {}", checks.join(", "));
+        }
+
+        println!(
+            "{year}: {} passed, {} failed, {} missing",
+            tally.passed, tally.failed, tally.missing
+        );
+        total.passed += tally.passed;
+        total.failed += tally.failed;
+        total.missing += tally.missing;
+    }
+
+    match total.failed {
+        0 => Ok(()),
+        failed => Err(RunError::Mismatch(failed)),
+    }
+}
+
+/// Compares one part, describing how it went
+fn check(output: &Output, answers: &DayAnswers, part: u8, tally: &mut Tally) -> String {
+    // Without an answer for the part there is no telling whether a wrong one
+    // that shows up somewhere was meant for it
+    let known_wrong = output
+        .answer(part)
+        .and_then(|got| Some((answers.known_wrong(part, &got)?, got)));
+
+    if let Some((why, wrong)) = known_wrong {
+        tally.failed += 1;
+        return format!("fail, {wrong} {why}");
+    }
+
+    let Some(expected) = answers.answer(part) else {
+        tally.missing += 1;
+        return "missing".to_string();
+    };
+
+    if output.has(part, &expected) {
+        tally.passed += 1;
+        return "pass".to_string();
+    }
+
+    tally.failed += 1;
+    match output.answer(part) {
+        Some(got) => format!("fail, got {got} instead of {expected}"),
+        None => format!("fail, {expected} is not in the output"),
+    }
+}
+
+/// Builds and runs the day on its input, asking for bare answers
+fn output(day: &Day, timeout: Duration) -> Result<Output, RunError> {
+    let binary = run::build(day)?;
+    let text = run::capture(
+        day,
+        &binary,
+        &run::Run::default(),
+        &[(FORMAT_VAR, "answers")],
+        timeout,
+    )?;
+
+    Ok(match day.is_solution() {
+        true => Output::Answers(text.lines().map(String::from).collect()),
+        false => Output::Text(text),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_has_whole_answer() {
+        let output = Output::Text(
+            "Santa ends up on floor 138\nThe byte that blocks is (28, 56)\n".to_string(),
+        );
+
+        assert!(output.has(1, "138"));
+        assert!(output.has(2, "28,56"));
+        assert!(output.has(2, "28"));
+        assert!(!output.has(1, "13"));
+        assert!(!output.has(1, "38"));
+        assert!(!output.has(1, "28"));
+        assert!(!output.has(2, "138"));
+    }
+
+    #[test]
+    fn test_has_anywhere() {
+        let output =
+            Output::Text("Loop 1 ends at 5\nLoop 2 ends at 7\nLoop 3 ends at 9\n".to_string());
+
+        assert!(output.has(1, "7"));
+        assert!(output.has(2, "9"));
+        assert!(!output.has(1, "10"));
+    }
+
+    #[test]
+    fn test_check() {
+        let answers = DayAnswers {
+            part1: Some(answers::Recorded::Number(138)),
+            part2: None,
+            part2_wrong: vec![answers::Recorded::Number(5)],
+            part2_too_high: vec![10],
+            ..Default::default()
+        };
+        let mut tally = Tally::default();
+
+        let output = Output::Answers(vec!["138".to_string(), "5".to_string()]);
+        assert_eq!(check(&output, &answers, 1, &mut tally), "pass");
+        assert_eq!(
+            check(&output, &answers, 2, &mut tally),
+            "fail, 5 is known to be wrong"
+        );
+
+        let output = Output::Answers(vec!["139".to_string(), "6".to_string()]);
+        assert_eq!(
+            check(&output, &answers, 1, &mut tally),
+            "fail, got 139 instead of 138"
+        );
+        assert_eq!(check(&output, &answers, 2, &mut tally), "missing");
+
+        let output = Output::Answers(vec!["138".to_string(), "12".to_string()]);
+        assert_eq!(check(&output, &answers, 1, &mut tally), "pass");
+        assert_eq!(
+            check(&output, &answers, 2, &mut tally),
+            "fail, 12 is too high"
+        );
+
+        assert_eq!((tally.passed, tally.failed, tally.missing), (2, 3, 1));
+    }
+
+    #[test]
+    fn test_check_sentences() {
+        let answers = DayAnswers {
+            part1: Some(answers::Recorded::Number(1485)),
+            part2: None,
+            part1_wrong: vec![answers::Recorded::Number(74)],
+            part2_wrong: vec![answers::Recorded::Number(2462)],
+            ..Default::default()
+        };
+        let mut tally = Tally::default();
+
+        let output =
+            Output::Text("There are 1485 sets\nAfter 74 rounds the password is 2462\n".to_string());
+        assert_eq!(check(&output, &answers, 1, &mut tally), "pass");
+        assert_eq!(
+            check(&output, &answers, 2, &mut tally),
+            "fail, 2462 is known to be wrong"
+        );
+
+        // With a third sentence the parts can not be told apart, so a wrong
+        // answer showing up is not held against either of them
+        let output = Output::Text(
+            "There are 74 sets\nThere are 1485 sets\nThe password is 2462\n".to_string(),
+        );
+        assert_eq!(check(&output, &answers, 1, &mut tally), "pass");
+        assert_eq!(check(&output, &answers, 2, &mut tally), "missing");
+
+        assert_eq!((tally.passed, tally.failed, tally.missing), (2, 1, 1));
+    }
+}
@@ -0,0 +1,268 @@
+use assert_cmd::cargo::cargo_bin_cmd;
+use assert_cmd::Command;
+use std::fs;
+use std::path::Path;
+
+/// A day that counts its input's lines, and says which part it was asked for
+const MAIN: &str = r#"use std::io::{self, prelude::*};
+
+fn main() {
+    let lines = io::stdin().lock().lines().count();
+    let part = std::env::var("AOC_PART").unwrap_or_default();
+    let args = std::env::args().skip(1).collect::<Vec<_>>().join(" ");
+    println!("{lines} lines, part [{part}], args [{args}]");
+}
+"#;
+
+fn day_folder(root: &Path, day: &str, main: &str, input: &str) -> std::io::Result<()> {
+    let dir = root.join("2030").join(day);
+    fs::create_dir_all(dir.join("src"))?;
+    fs::write(
+        dir.join("Cargo.toml"),
+        format!(
+            "[package]\nname = \"{day}\"\nversion = \"0.1.0\"\nedition = \"2021\"\n\n[workspace]\n"
+        ),
+    )?;
+    fs::write(dir.join("src/main.rs"), main)?;
+    fs::write(dir.join("input.txt"), input)
+}
+
+/// Says in the day's Cargo.toml that it is built on `Solution`
+fn mark_solution(root: &Path, day: &str) -> std::io::Result<()> {
+    let manifest = root.join("2030").join(day).join("Cargo.toml");
+    let text = fs::read_to_string(&manifest)?;
+    fs::write(
+        manifest,
+        text + "\n[package.metadata.aoc]\nsolution = true\n",
+    )
+}
+
+fn aoc(root: &Path) -> Command {
+    let mut cmd = cargo_bin_cmd!("aoc");
+    cmd.arg("--root")
+        .arg(root)
+        .env("CARGO_TARGET_DIR", root.join("target"));
+    cmd
+}
+
+#[test]
+fn test_run_day() -> Result<(), Box<dyn std::error::Error>> {
+    let root = tempfile::tempdir()?;
+    day_folder(root.path(), "day_01", MAIN, "1\n2\n3\n")?;
+    let other_input = root.path().join("other.txt");
+    fs::write(&other_input, "1\n")?;
+
+    aoc(root.path())
+        .args(["run", "2030", "1"])
+        .assert()
+        .success()
+        .stdout("3 lines, part [], args []\n");
+
+    aoc(root.path())
+        .args(["run", "2030", "1", "--part", "2", "--input"])
+        .arg(&other_input)
+        .args(["--", "7", "12"])
+        .assert()
+        .success()
+        .stdout("1 lines, part [2], args [7 12]\n")
+        .stderr(predicates::str::contains(
+            "2030 day 01 is not a Solution day, so it answers both parts",
+        ));
+
+    Ok(())
+}
+
+#[test]
+fn test_run_all() -> Result<(), Box<dyn std::error::Error>> {
+    let root = tempfile::tempdir()?;
+    day_folder(root.path(), "day_01", MAIN, "1\n")?;
+    day_folder(root.path(), "day_02", MAIN, "")?;
+    day_folder(root.path(), "day_03", "fn main() { panic!() }", "1\n")?;
+    day_folder(root.path(), "day_04", MAIN, "1\n2\n")?;
+
+    aoc(root.path())
+        .args(["run", "2030", "--all"])
+        .assert()
+        .code(70)
+        .stdout(
+            "== 2030 day 01\n1 lines, part [], args []\n\
+             == 2030 day 02: no input, skipped\n\
+             == 2030 day 03\n\
+             == 2030 day 04\n2 lines, part [], args []\n",
+        )
+        .stderr(predicates::str::contains("failed: 2030 day 03"));
+
+    Ok(())
+}
+
+#[test]
+fn test_run_missing() -> Result<(), Box<dyn std::error::Error>> {
+    let root = tempfile::tempdir()?;
+    day_folder(root.path(), "day_01", MAIN, "1\n")?;
+
+    aoc(root.path())
+        .args(["run", "2030", "2"])
+        .assert()
+        .code(66)
+        .stderr("Error: 2030 has no day 2\n");
+
+    aoc(root.path())
+        .args(["run", "2031", "1"])
+        .assert()
+        .code(66);
+
+    aoc(root.path())
+        .args(["run", "2030", "1", "--input", "nowhere.txt"])
+        .assert()
+        .code(66);
+
+    Ok(())
+}
+
+#[test]
+fn test_verify() -> Result<(), Box<dyn std::error::Error>> {
+    let root = tempfile::tempdir()?;
+    day_folder(root.path(), "day_01", MAIN, "1\n2\n3\n")?;
+    day_folder(root.path(), "day_02", SENTENCE_MAIN, "1\n")?;
+    day_folder(root.path(), "day_03", "fn main() { loop {} }", "1\n")?;
+    day_folder(root.path(), "day_04", MAIN, "1\n")?;
+    fs::write(
+        root.path().join("2030/answers.toml"),
+        "[day_01]\npart1 = 3\n\n[day_02]\npart1 = 2\npart2_wrong = [1795]\n\n[day_03]\npart1 = 1\n",
+    )?;
+
+    aoc(root.path())
+        .args(["verify", "--timeout", "1"])
+        .assert()
+        .code(1)
+        .stdout(
+            "2030 day 01: part 1 pass, part 2 missing\n\
+             2030 day 02: part 1 fail, got 138 instead of 2, part 2 fail, 1795 is known to be wrong\n\
+             2030 day 03: 2030 day 03 took longer than 1s\n\
+             2030 day 04: missing\n\
+             2030: 1 passed, 4 failed, 3 missing\n",
+        )
+        .stderr("Error: 4 answers did not match\n");
+
+    fs::write(
+        root.path().join("2030/answers.toml"),
+        "[day_01]\npart1 = 3\n",
+    )?;
+    aoc(root.path()).args(["verify", "2030"]).assert().success();
+
+    Ok(())
+}
+
+/// A day that reports fixed timings the way `Solution` days do
+const BENCH_MAIN: &str = r#"fn main() {
+    assert_eq!(std::env::var("AOC_FORMAT").unwrap(), "bench");
+    assert_eq!(std::env::var("AOC_ITERATIONS").unwrap(), "3");
+    println!("parse 1000 3000 2000");
+    println!("part1 5000000 4000000 6000000");
+    println!("part2 7000 7000 7000");
+}
+"#;
+
+#[test]
+fn test_bench() -> Result<(), Box<dyn std::error::Error>> {
+    let root = tempfile::tempdir()?;
+    day_folder(root.path(), "day_01", MAIN, "1\n")?;
+    day_folder(root.path(), "day_02", BENCH_MAIN, "1\n")?;
+    mark_solution(root.path(), "day_02")?;
+    let saved = root.path().join("timings.json");
+
+    aoc(root.path())
+        .args(["bench", "2030", "--iterations", "3", "--save"])
+        .arg(&saved)
+        .assert()
+        .success()
+        .stdout(predicates::str::contains(
+            "day 02       2.0µs    5.00ms     7.0µs    4.01ms    5.01ms    6.01ms\n",
+        ))
+        .stdout(predicates::str::is_match(r"day 01 +- +- +- ")?);
+
+    let timings: serde_json::Value = serde_json::from_str(&fs::read_to_string(&saved)?)?;
+    assert_eq!(timings["2030/day_02"]["part1"]["median_ms"], 5.0);
+    assert!(timings["2030/day_01"]["part1"].is_null());
+
+    fs::write(
+        &saved,
+        r#"{"2030/day_02": {"parse": null, "part1": null, "part2": null,
+            "total": {"min_ms": 1.0, "median_ms": 1.0, "max_ms": 1.0}}}"#,
+    )?;
+    aoc(root.path())
+        .args(["bench", "2030", "--iterations", "3", "--baseline"])
+        .arg(&saved)
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains(
+            "2030/day_02 is 401% slower than the baseline, 5.01ms was 1.00ms\n",
+        ))
+        .stderr("Error: 1 day got slower\n");
+
+    Ok(())
+}
+
+/// A day that says its answers in sentences, between its debug prints
+const SENTENCE_MAIN: &str = r#"fn main() {
+    println!("Cube {{ x: 1, y: 2 }}");
+    println!("Santa ends up on floor 138.");
+    println!("Santa first reaches the basement at position 1795.");
+}
+"#;
+
+/// A day that answers the way `Solution` days do with `AOC_FORMAT=json`
+const JSON_MAIN: &str = r##"fn main() {
+    assert_eq!(std::env::var("AOC_FORMAT").unwrap(), "json");
+    println!(r#"{{"part1":"10092","part2":null,"timings_ms":{{"parse":0.5,"part1":2.0}}}}"#);
+}
+"##;
+
+#[test]
+fn test_run_json() -> Result<(), Box<dyn std::error::Error>> {
+    let root = tempfile::tempdir()?;
+    day_folder(root.path(), "day_01", MAIN, "1\n2\n")?;
+    day_folder(root.path(), "day_02", MAIN, "")?;
+    day_folder(root.path(), "day_03", SENTENCE_MAIN, "1\n")?;
+    day_folder(root.path(), "day_15", JSON_MAIN, "1\n")?;
+    mark_solution(root.path(), "day_15")?;
+
+    let output = aoc(root.path())
+        .args(["run", "2030", "--all", "--format", "json", "--part", "1"])
+        .assert()
+        .success()
+        .stderr(
+            "2030 day 01 is not a Solution day, so it answers both parts whatever --part says\n\
+             2030 day 02: no input, skipped\n\
+             2030 day 03 is not a Solution day, so it answers both parts whatever --part says\n",
+        )
+        .get_output()
+        .stdout
+        .clone();
+
+    let reports = String::from_utf8(output)?
+        .lines()
+        .map(serde_json::from_str)
+        .collect::<Result<Vec<serde_json::Value>, _>>()?;
+    assert_eq!(reports.len(), 3);
+
+    assert_eq!(reports[0]["year"], 2030);
+    assert_eq!(reports[0]["day"], 1);
+    assert!(reports[0]["part1"].is_null());
+    assert_eq!(reports[0]["output"], "2 lines, part [1], args []\n");
+    assert!(reports[0]["timings_ms"]["total"].as_f64().is_some());
+
+    assert_eq!(reports[1]["day"], 3);
+    assert_eq!(reports[1]["part1"], "138");
+    assert!(reports[1]["part2"].is_null());
+    assert!(reports[1]["output"].as_str().is_some());
+
+    assert_eq!(reports[2]["day"], 15);
+    assert_eq!(reports[2]["part1"], "10092");
+    assert!(reports[2]["part2"].is_null());
+    assert!(reports[2].get("output").is_none());
+    assert_eq!(reports[2]["timings_ms"]["part1"], 2.0);
+    assert!(reports[2]["timings_ms"]["total"].as_f64().is_some());
+
+    Ok(())
+}
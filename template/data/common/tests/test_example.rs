@@ -0,0 +1,46 @@
+use assert_cmd::cargo::cargo_bin_cmd;
+use std::fs;
+use std::path::PathBuf;
+
+/// Runs every `examples/NAME.txt` through the solution and checks the output
+/// matches `examples/NAME.expected`
+#[test]
+fn test_examples() -> Result<(), Box<dyn std::error::Error>> {
+    let examples_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("examples");
+
+    let mut inputs = fs::read_dir(&examples_dir)?
+        .map(|entry| entry.map(|e| e.path()))
+        .collect::<Result<Vec<_>, _>>()?;
+    inputs.retain(|p| p.extension().is_some_and(|ext| ext == "txt"));
+    inputs.sort();
+
+    assert!(
+        !inputs.is_empty(),
+        "No examples found in {}",
+        examples_dir.display()
+    );
+
+    let mut failures = Vec::new();
+
+    for input in inputs {
+        let expected_path = input.with_extension("expected");
+        let expected = fs::read_to_string(&expected_path)
+            .map_err(|e| format!("{}: {e}", expected_path.display()))?;
+
+        let output = cargo_bin_cmd!("{{crate_name}}")
+            .write_stdin(fs::read_to_string(&input)?)
+            .output()?;
+        let actual = String::from_utf8_lossy(&output.stdout);
+
+        if actual != expected {
+            failures.push(format!(
+                "{}\n--- expected\n{expected}--- actual\n{actual}",
+                input.display()
+            ));
+        }
+    }
+
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+
+    Ok(())
+}
@@ -0,0 +1,24 @@
+use aoc_common::{Answer, Grid, Solution};
+
+pub struct Day{{day_padded}};
+
+impl Solution for Day{{day_padded}} {
+    type Input = Grid<char>;
+
+    fn parse(input: &str) -> Self::Input {
+        let lines = input.lines().collect::<Vec<_>>();
+        Grid::parse(&lines).expect("The map should be a rectangle")
+    }
+
+    fn part1(map: &Self::Input) -> Answer {
+        map.find_all(|&c| c == '#')
+            .flat_map(|wall| map.neighbours4(wall))
+            .filter(|(_, &c)| c == '.')
+            .count()
+            .into()
+    }
+
+    fn part2(_map: &Self::Input) -> Answer {
+        Answer::Unsolved
+    }
+}
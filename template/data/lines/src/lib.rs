@@ -0,0 +1,19 @@
+use aoc_common::{Answer, Solution};
+
+pub struct Day{{day_padded}};
+
+impl Solution for Day{{day_padded}} {
+    type Input = Vec<String>;
+
+    fn parse(input: &str) -> Self::Input {
+        input.lines().map(String::from).collect()
+    }
+
+    fn part1(_lines: &Self::Input) -> Answer {
+        Answer::Unsolved
+    }
+
+    fn part2(_lines: &Self::Input) -> Answer {
+        Answer::Unsolved
+    }
+}
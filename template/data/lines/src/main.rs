@@ -0,0 +1,8 @@
+use {{crate_name}}::Day{{day_padded}};
+
+fn main() {
+    aoc_common::solution::run::<Day{{day_padded}}>([
+        "The answer to part 1 is {}",
+        "The answer to part 2 is {}",
+    ]);
+}
@@ -0,0 +1,45 @@
+use aoc_common::{
+    parse::{self, key_value, space_list, unsigned},
+    Answer, Solution,
+};
+
+use nom::{character::complete::alpha1, IResult};
+
+#[derive(Debug)]
+pub struct Entry {
+    pub name: String,
+    pub values: Vec<u32>,
+}
+
+fn parse_entry(input: &str) -> IResult<&str, Entry> {
+    let (input, (name, values)) = key_value(alpha1, space_list(unsigned))(input)?;
+    Ok((
+        input,
+        Entry {
+            name: name.to_string(),
+            values,
+        },
+    ))
+}
+
+pub struct Day{{day_padded}};
+
+impl Solution for Day{{day_padded}} {
+    type Input = Vec<Entry>;
+
+    fn parse(input: &str) -> Self::Input {
+        parse::parse_each_line(input, parse_entry).expect("Every line should be an entry")
+    }
+
+    fn part1(entries: &Self::Input) -> Answer {
+        entries
+            .iter()
+            .max_by_key(|e| e.values.iter().sum::<u32>())
+            .map(|e| e.name.as_str())
+            .into()
+    }
+
+    fn part2(_entries: &Self::Input) -> Answer {
+        Answer::Unsolved
+    }
+}
@@ -0,0 +1,96 @@
+use aoc_common::{Answer, Solution};
+
+#[derive(Debug, Clone, Copy)]
+pub enum Instruction {
+    Nop,
+    Add(isize),
+    Jump(isize),
+}
+
+impl Instruction {
+    fn parse(line: &str) -> Option<Self> {
+        let mut parts = line.split_whitespace();
+        let op = parts.next()?;
+        let mut argument = || parts.next()?.parse::<isize>().ok();
+
+        match op {
+            "nop" => Some(Instruction::Nop),
+            "add" => Some(Instruction::Add(argument()?)),
+            "jmp" => Some(Instruction::Jump(argument()?)),
+            _ => None,
+        }
+    }
+
+    fn execute(&self, computer: &mut Computer) {
+        match self {
+            Instruction::Nop => computer.program_counter += 1,
+            Instruction::Add(value) => {
+                computer.accumulator += value;
+                computer.program_counter += 1;
+            }
+            Instruction::Jump(offset) => {
+                computer.program_counter = computer.program_counter.wrapping_add_signed(*offset)
+            }
+        }
+    }
+}
+
+struct Computer {
+    accumulator: isize,
+    program: Vec<Instruction>,
+    program_counter: usize,
+    cycles: usize,
+}
+
+impl Computer {
+    fn new(program: Vec<Instruction>) -> Self {
+        Computer {
+            accumulator: 0,
+            program,
+            program_counter: 0,
+            cycles: 0,
+        }
+    }
+
+    fn step(&mut self) -> bool {
+        let Some(instruction) = self.program.get(self.program_counter).copied() else {
+            return false;
+        };
+
+        instruction.execute(self);
+        self.cycles += 1;
+
+        true
+    }
+
+    fn run(&mut self) {
+        while self.step() {}
+    }
+}
+
+pub struct Day{{day_padded}};
+
+impl Solution for Day{{day_padded}} {
+    type Input = Vec<Instruction>;
+
+    fn parse(input: &str) -> Self::Input {
+        input
+            .lines()
+            .map(|l| Instruction::parse(l).expect("Unknown instruction"))
+            .collect()
+    }
+
+    fn part1(program: &Self::Input) -> Answer {
+        let mut computer = Computer::new(program.clone());
+        computer.run();
+
+        computer.accumulator.into()
+    }
+
+    fn part2(program: &Self::Input) -> Answer {
+        let mut computer = Computer::new(program.clone());
+        computer.run();
+
+        computer.cycles.into()
+    }
+}
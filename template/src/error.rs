@@ -0,0 +1,99 @@
+use std::fmt;
+use std::io;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use crate::template::TemplateError;
+
+#[derive(Debug)]
+pub enum BuildError {
+    /// The day folder is already there and `--force` was not given
+    DayExists(PathBuf),
+    /// A file the template needs could not be read
+    TemplateMissing { path: PathBuf, source: io::Error },
+    /// A template file could not be rendered
+    Template {
+        path: PathBuf,
+        source: TemplateError,
+    },
+    /// There is no day folder to fetch the input into
+    DayMissing(PathBuf),
+    /// The input has already been filled in and `--force` was not given
+    InputExists(PathBuf),
+    /// Downloading needs a session token and none was given
+    NoSession,
+    /// The input could not be downloaded
+    Fetch { url: String, message: String },
+    /// Anything else that went wrong touching the disk
+    Io { path: PathBuf, source: io::Error },
+}
+
+impl BuildError {
+    pub fn io(path: impl Into<PathBuf>, source: io::Error) -> Self {
+        BuildError::Io {
+            path: path.into(),
+            source,
+        }
+    }
+
+    /// Exit codes follow sysexits.h so scripts can tell the failures apart
+    pub fn exit_code(&self) -> ExitCode {
+        match self {
+            BuildError::DayExists(_) => ExitCode::from(73),
+            BuildError::TemplateMissing { .. } => ExitCode::from(66),
+            BuildError::Template { .. } => ExitCode::from(65),
+            BuildError::DayMissing(_) => ExitCode::from(66),
+            BuildError::InputExists(_) => ExitCode::from(73),
+            BuildError::NoSession => ExitCode::from(78),
+            BuildError::Fetch { .. } => ExitCode::from(69),
+            BuildError::Io { .. } => ExitCode::from(74),
+        }
+    }
+}
+
+impl fmt::Display for BuildError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BuildError::DayExists(path) => write!(
+                f,
+                "{} already exists, use --force to overwrite it",
+                path.display()
+            ),
+            BuildError::TemplateMissing { path, source } => {
+                write!(f, "unable to read template {}: {source}", path.display())
+            }
+            BuildError::Template { path, source } => {
+                write!(f, "unable to render template {}, {source}", path.display())
+            }
+            BuildError::DayMissing(path) => {
+                write!(f, "{} does not exist, create the day first", path.display())
+            }
+            BuildError::InputExists(path) => write!(
+                f,
+                "{} is not empty, use --force to overwrite it",
+                path.display()
+            ),
+            BuildError::NoSession => {
+                write!(f, "no session token, pass --session or set AOC_SESSION")
+            }
+            BuildError::Fetch { url, message } => write!(f, "unable to download {url}: {message}"),
+            BuildError::Io { path, source } => write!(f, "{}: {source}", path.display()),
+        }
+    }
+}
+
+impl std::error::Error for BuildError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            BuildError::DayExists(_)
+            | BuildError::DayMissing(_)
+            | BuildError::InputExists(_)
+            | BuildError::NoSession
+            | BuildError::Fetch { .. } => None,
+            BuildError::Template { source, .. } => Some(source),
+            BuildError::TemplateMissing { source, .. } | BuildError::Io { source, .. } => {
+                Some(source)
+            }
+        }
+    }
+}
@@ -0,0 +1,134 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::Duration;
+
+use crate::error::BuildError;
+
+/// Touched on every download so the next one knows when to wait until
+const LAST_REQUEST: &str = "last_request";
+
+/// Advent of Code asks tools to say who they are
+const USER_AGENT: &str = concat!(
+    env!("CARGO_PKG_NAME"),
+    "/",
+    env!("CARGO_PKG_VERSION"),
+    " by ",
+    env!("CARGO_PKG_AUTHORS")
+);
+
+pub struct Fetch<'a> {
+    pub root: &'a Path,
+    pub year: u16,
+    pub day: u8,
+    pub session: Option<&'a str>,
+    pub base_url: &'a str,
+    pub cache_dir: &'a Path,
+    pub throttle: Duration,
+    pub force: bool,
+}
+
+/// Saves the day's puzzle input into its `input.txt`. An input is only ever
+/// downloaded once, after that it comes from the cache.
+pub fn fetch(options: &Fetch) -> Result<(), BuildError> {
+    let day_dir = options
+        .root
+        .join(options.year.to_string())
+        .join(format!("day_{:02}", options.day));
+    if !day_dir.is_dir() {
+        return Err(BuildError::DayMissing(day_dir));
+    }
+
+    let input_path = day_dir.join("input.txt");
+    let existing = fs::read_to_string(&input_path).unwrap_or_default();
+    if !existing.trim().is_empty() && !options.force {
+        return Err(BuildError::InputExists(input_path));
+    }
+
+    let cached = options
+        .cache_dir
+        .join(options.year.to_string())
+        .join(format!("day_{:02}.txt", options.day));
+
+    let input = match fs::read_to_string(&cached) {
+        Ok(input) => {
+            println!("Using the cached input from {}", cached.display());
+            input
+        }
+        Err(_) => {
+            let input = download(options)?;
+            if let Some(parent) = cached.parent() {
+                fs::create_dir_all(parent).map_err(|e| BuildError::io(parent, e))?;
+            }
+            fs::write(&cached, &input).map_err(|e| BuildError::io(&cached, e))?;
+            input
+        }
+    };
+
+    fs::write(&input_path, input).map_err(|e| BuildError::io(&input_path, e))?;
+    println!("Saved the input to {}", input_path.display());
+
+    Ok(())
+}
+
+fn download(options: &Fetch) -> Result<String, BuildError> {
+    let session = options.session.ok_or(BuildError::NoSession)?;
+    let url = format!(
+        "{}/{}/day/{}/input",
+        options.base_url.trim_end_matches('/'),
+        options.year,
+        options.day
+    );
+
+    throttle(options.cache_dir, options.throttle)?;
+    println!("Downloading {url}");
+
+    let fetch_error = |message: String| BuildError::Fetch {
+        url: url.clone(),
+        message,
+    };
+
+    let mut response = ureq::get(&url)
+        .header("Cookie", &format!("session={session}"))
+        .header("User-Agent", USER_AGENT)
+        .call()
+        .map_err(|e| match e {
+            ureq::Error::StatusCode(400 | 401) => {
+                fetch_error("the session token was not accepted".to_string())
+            }
+            ureq::Error::StatusCode(404) => fetch_error("the puzzle is not out yet".to_string()),
+            e => fetch_error(e.to_string()),
+        })?;
+
+    response
+        .body_mut()
+        .read_to_string()
+        .map_err(|e| fetch_error(e.to_string()))
+}
+
+/// Waits until at least `interval` has passed since the last download
+fn throttle(cache_dir: &Path, interval: Duration) -> Result<(), BuildError> {
+    let marker = cache_dir.join(LAST_REQUEST);
+
+    let elapsed = fs::metadata(&marker)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|modified| modified.elapsed().ok());
+
+    if let Some(wait) = elapsed.and_then(|e| interval.checked_sub(e)) {
+        println!("Waiting {:.1}s before asking again", wait.as_secs_f32());
+        thread::sleep(wait);
+    }
+
+    fs::create_dir_all(cache_dir).map_err(|e| BuildError::io(cache_dir, e))?;
+    fs::write(&marker, "").map_err(|e| BuildError::io(&marker, e))
+}
+
+/// `$XDG_CACHE_HOME/aoc`, falling back to `~/.cache/aoc`
+pub fn default_cache_dir() -> PathBuf {
+    std::env::var_os("XDG_CACHE_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".cache")))
+        .unwrap_or_else(|| PathBuf::from(".cache"))
+        .join("aoc")
+}
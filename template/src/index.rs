@@ -0,0 +1,284 @@
+//! Keeps the readme indexes and workspaces pointing at every day. Each function
+//! returns `None` when there is nothing to change, so running them twice never
+//! adds a duplicate.
+
+const DAYS_HEADING: &str = "## Days";
+const YEARS_HEADING: &str = "## Years";
+
+/// Adds `- [title](day_NN/readme.md)` to the days list of a year readme,
+/// creating the list at the end when there isn't one
+pub fn add_day_link(readme: &str, day: u8, title: &str) -> Option<String> {
+    let target = format!("(day_{:02}/readme.md)", day);
+    if readme.contains(&target) {
+        return None;
+    }
+
+    let entry = format!("- [{title}]{target}");
+    let mut lines: Vec<&str> = readme.lines().collect();
+
+    let Some(heading) = lines.iter().position(|l| l.trim() == DAYS_HEADING) else {
+        let mut out = readme.trim_end().to_string();
+        out.push_str(&format!("\n\n{DAYS_HEADING}\n\n{entry}\n"));
+        return Some(out);
+    };
+
+    let list_start = heading + 1;
+    let list_end = lines[list_start..]
+        .iter()
+        .position(|l| l.starts_with('#'))
+        .map_or(lines.len(), |p| p + list_start);
+
+    let insert_at = (list_start..list_end)
+        .find(|&ix| linked_day(lines[ix]).is_some_and(|d| d > day))
+        .or_else(|| {
+            (list_start..list_end)
+                .rev()
+                .find(|&ix| linked_day(lines[ix]).is_some())
+                .map(|ix| ix + 1)
+        });
+
+    match insert_at {
+        Some(ix) => lines.insert(ix, &entry),
+        None if lines.get(list_start).is_some_and(|l| l.trim().is_empty()) => {
+            lines.insert(list_start + 1, &entry)
+        }
+        None => {
+            lines.insert(list_start, "");
+            lines.insert(list_start + 1, &entry);
+        }
+    }
+
+    Some(with_trailing_newline(lines.join("\n"), readme))
+}
+
+/// Adds `[YYYY](YYYY)` to the years list of the root readme, newest first.
+/// A readme without a years list is left alone.
+pub fn add_year_link(readme: &str, year: u16) -> Option<String> {
+    let entry = format!("[{year}]({year})");
+    if readme.lines().any(|l| l.trim() == entry) {
+        return None;
+    }
+
+    let mut lines: Vec<&str> = readme.lines().collect();
+
+    let heading = lines.iter().position(|l| l.trim() == YEARS_HEADING)?;
+
+    let years = (heading + 1..lines.len())
+        .take_while(|&ix| !lines[ix].starts_with('#'))
+        .filter_map(|ix| linked_year(lines[ix]).map(|y| (ix, y)))
+        .collect::<Vec<_>>();
+
+    let insert_at = match years.iter().find(|(_, y)| *y < year) {
+        Some((ix, _)) => *ix,
+        None => years.last().map_or(heading + 1, |(ix, _)| ix + 1),
+    };
+
+    lines.insert(insert_at, &entry);
+
+    Some(with_trailing_newline(lines.join("\n"), readme))
+}
+
+/// Adds the crate to the workspace members unless a pattern already covers it
+pub fn add_workspace_member(cargo_toml: &str, crate_name: &str) -> Option<String> {
+    let start = cargo_toml.find("members")?;
+    let open = start + cargo_toml[start..].find('[')?;
+    let close = open + cargo_toml[open..].find(']')?;
+
+    let members = cargo_toml[open + 1..close]
+        .split(',')
+        .map(|m| m.trim().trim_matches('"'))
+        .filter(|m| !m.is_empty())
+        .collect::<Vec<_>>();
+
+    if members.iter().any(|m| member_matches(m, crate_name)) {
+        return None;
+    }
+
+    let mut members = members;
+    members.push(crate_name);
+    members.sort();
+
+    let list = members
+        .iter()
+        .map(|m| format!("\"{m}\""))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    Some(format!(
+        "{}[{list}]{}",
+        &cargo_toml[..open],
+        &cargo_toml[close + 1..]
+    ))
+}
+
+/// Adds a `[day_NN]` table with the day's answers to a year's answers.toml,
+/// keeping the days in order. A day that is already there is left alone.
+pub fn add_answers(answers_toml: &str, day: u8, answers: &[String]) -> Option<String> {
+    let header = format!("[day_{:02}]", day);
+    if answers.is_empty() || answers_toml.lines().any(|l| l.trim() == header) {
+        return None;
+    }
+
+    let mut table = header;
+    for (part, answer) in answers.iter().take(2).enumerate() {
+        table.push_str(&format!("\npart{} = {}", part + 1, toml_value(answer)));
+    }
+
+    let lines: Vec<&str> = answers_toml.lines().collect();
+    let later_day = lines
+        .iter()
+        .position(|l| table_day(l).is_some_and(|d| d > day));
+
+    Some(match later_day {
+        Some(ix) => {
+            let before = lines[..ix].join("\n");
+            let after = lines[ix..].join("\n");
+            let before = match before.is_empty() {
+                true => before,
+                false => format!("{before}\n"),
+            };
+            with_trailing_newline(format!("{before}{table}\n\n{after}"), answers_toml)
+        }
+        None if answers_toml.trim().is_empty() => format!("{table}\n"),
+        None => format!("{}\n\n{table}\n", answers_toml.trim_end()),
+    })
+}
+
+/// Numbers stay bare, anything else is quoted
+fn toml_value(answer: &str) -> String {
+    match answer.parse::<i64>() {
+        Ok(n) => n.to_string(),
+        Err(_) => format!("\"{}\"", answer.replace('\\', "\\\\").replace('"', "\\\"")),
+    }
+}
+
+fn member_matches(member: &str, crate_name: &str) -> bool {
+    match member.strip_suffix('*') {
+        Some(prefix) => crate_name.starts_with(prefix),
+        None => member == crate_name,
+    }
+}
+
+fn linked_day(line: &str) -> Option<u8> {
+    let start = line.find("(day_")? + "(day_".len();
+    let digits = line[start..].split('/').next()?;
+    digits.parse().ok()
+}
+
+fn table_day(line: &str) -> Option<u8> {
+    let line = line.trim();
+    line.strip_prefix("[day_")?.strip_suffix(']')?.parse().ok()
+}
+
+fn linked_year(line: &str) -> Option<u16> {
+    let line = line.trim();
+    let (text, link) = line.strip_prefix('[')?.split_once("](")?;
+    let link = link.strip_suffix(')')?;
+
+    if text != link {
+        return None;
+    }
+
+    link.parse().ok()
+}
+
+fn with_trailing_newline(mut text: String, original: &str) -> String {
+    if original.ends_with('\n') {
+        text.push('\n');
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_day_list_is_created() {
+        let readme = "# Advent of Code in Rust 2025\n\n### Needs more attention\n";
+
+        assert_eq!(
+            add_day_link(readme, 3, "Day 3"),
+            Some("# Advent of Code in Rust 2025\n\n### Needs more attention\n\n## Days\n\n- [Day 3](day_03/readme.md)\n".to_string())
+        );
+    }
+
+    #[test]
+    fn test_days_stay_sorted() {
+        let readme = "## Days\n\n- [Day 2](day_02/readme.md)\n- [Day 9](day_09/readme.md)\n";
+
+        assert_eq!(
+            add_day_link(readme, 5, "Day 5"),
+            Some("## Days\n\n- [Day 2](day_02/readme.md)\n- [Day 5](day_05/readme.md)\n- [Day 9](day_09/readme.md)\n".to_string())
+        );
+        assert_eq!(
+            add_day_link(readme, 1, "Day 1"),
+            Some("## Days\n\n- [Day 1](day_01/readme.md)\n- [Day 2](day_02/readme.md)\n- [Day 9](day_09/readme.md)\n".to_string())
+        );
+        assert_eq!(
+            add_day_link(readme, 12, "Day 12"),
+            Some("## Days\n\n- [Day 2](day_02/readme.md)\n- [Day 9](day_09/readme.md)\n- [Day 12](day_12/readme.md)\n".to_string())
+        );
+    }
+
+    #[test]
+    fn test_day_is_not_duplicated() {
+        let readme = "## Days\n\n- [Day 2](day_02/readme.md)\n";
+
+        assert_eq!(add_day_link(readme, 2, "Day 2: Something"), None);
+    }
+
+    #[test]
+    fn test_years_stay_sorted() {
+        let readme =
+            "## Years\n[2025](2025)\nThis is now!\n\n[2024](2024)\n[2021](2021)\nThis is not now\n";
+
+        assert_eq!(
+            add_year_link(readme, 2026),
+            Some("## Years\n[2026](2026)\n[2025](2025)\nThis is now!\n\n[2024](2024)\n[2021](2021)\nThis is not now\n".to_string())
+        );
+        assert_eq!(
+            add_year_link(readme, 2022),
+            Some("## Years\n[2025](2025)\nThis is now!\n\n[2024](2024)\n[2022](2022)\n[2021](2021)\nThis is not now\n".to_string())
+        );
+        assert_eq!(
+            add_year_link(readme, 2015),
+            Some("## Years\n[2025](2025)\nThis is now!\n\n[2024](2024)\n[2021](2021)\n[2015](2015)\nThis is not now\n".to_string())
+        );
+        assert_eq!(add_year_link(readme, 2024), None);
+        assert_eq!(add_year_link("# No years here\n", 2024), None);
+    }
+
+    #[test]
+    fn test_answers_stay_sorted() {
+        let answers = |a: &[&str]| a.iter().map(|a| a.to_string()).collect::<Vec<_>>();
+        let toml = "# Answers\n\n[day_03]\npart1 = 1\n";
+
+        let toml = add_answers(toml, 7, &answers(&["4,3,0", "12"])).unwrap();
+        let toml = add_answers(&toml, 1, &answers(&["38"])).unwrap();
+        assert_eq!(
+            toml,
+            "# Answers\n\n[day_01]\npart1 = 38\n\n[day_03]\npart1 = 1\n\n[day_07]\npart1 = \"4,3,0\"\npart2 = 12\n"
+        );
+
+        assert_eq!(add_answers(&toml, 3, &answers(&["2"])), None);
+        assert_eq!(add_answers(&toml, 4, &[]), None);
+        assert_eq!(
+            add_answers("", 4, &answers(&["5"])),
+            Some("[day_04]\npart1 = 5\n".to_string())
+        );
+    }
+
+    #[test]
+    fn test_workspace_members() {
+        let glob = "[workspace]\nresolver = \"2\"\nmembers = [\"day_*\"]\n";
+        assert_eq!(add_workspace_member(glob, "day_04"), None);
+
+        let listed = "[workspace]\nmembers = [\n    \"day_01\",\n]\n";
+        assert_eq!(
+            add_workspace_member(listed, "day_04"),
+            Some("[workspace]\nmembers = [\"day_01\", \"day_04\"]\n".to_string())
+        );
+        assert_eq!(add_workspace_member(listed, "day_01"), None);
+    }
+}
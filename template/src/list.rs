@@ -0,0 +1,177 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::error::BuildError;
+use crate::migrate::is_day_folder;
+
+/// The first year there was an Advent of Code
+const FIRST_YEAR: u16 = 2015;
+
+/// Prints every year under the root with the days it has, followed by the days
+/// that still need something: an input, an example or a solution.
+pub fn list(root: &Path) -> Result<(), BuildError> {
+    let mut years = subfolders(root)?
+        .into_iter()
+        .filter_map(|path| {
+            let year = path.file_name()?.to_str()?.parse::<u16>().ok()?;
+            (year >= FIRST_YEAR).then_some((year, path))
+        })
+        .collect::<Vec<_>>();
+    years.sort();
+
+    for (year, path) in years {
+        let days = subfolders(&path)?
+            .into_iter()
+            .filter(|p| is_day_folder(p))
+            .collect::<Vec<_>>();
+
+        let numbers = days
+            .iter()
+            .filter_map(|d| day_number(d))
+            .collect::<Vec<_>>();
+        match numbers.len() {
+            0 => println!("{year}: no days"),
+            1 => println!("{year}: 1 day ({})", ranges(&numbers)),
+            count => println!("{year}: {count} days ({})", ranges(&numbers)),
+        }
+
+        for day in &days {
+            let problems = problems(day);
+            if !problems.is_empty() {
+                let name = day.file_name().unwrap_or_default().to_string_lossy();
+                println!("  {name}: {}", problems.join(", "));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Everything that makes the day look unfinished
+fn problems(day: &Path) -> Vec<String> {
+    let mut problems = Vec::new();
+
+    if is_stub(day) {
+        problems.push("stub".to_string());
+    }
+
+    match fs::read_to_string(day.join("input.txt")) {
+        Ok(text) if text.trim().is_empty() => problems.push("empty input.txt".to_string()),
+        Ok(_) => {}
+        Err(_) => problems.push("no input.txt".to_string()),
+    }
+
+    let examples = example_files(day);
+    if examples.is_empty() {
+        problems.push("no example".to_string());
+    }
+    for example in examples {
+        if fs::read_to_string(&example).is_ok_and(|t| t.trim().is_empty()) {
+            let name = example.strip_prefix(day).unwrap_or(&example);
+            problems.push(format!("empty {}", name.display()));
+        }
+    }
+
+    problems
+}
+
+/// A day is a stub while neither part has an answer. Days built on
+/// `Solution` start with both parts `Answer::Unsolved`, older days started
+/// with `main` as the only function in src.
+fn is_stub(day: &Path) -> bool {
+    if let Ok(lib) = fs::read_to_string(day.join("src/lib.rs")) {
+        return lib.matches("Answer::Unsolved").count() >= 2;
+    }
+
+    let functions = files_in(&day.join("src"))
+        .iter()
+        .filter(|p| p.extension().is_some_and(|e| e == "rs"))
+        .filter_map(|p| fs::read_to_string(p).ok())
+        .map(|text| {
+            text.split(|c: char| !c.is_alphanumeric() && c != '_')
+                .filter(|word| *word == "fn")
+                .count()
+        })
+        .sum::<usize>();
+
+    functions <= 1
+}
+
+/// The old style `example*.txt` next to the input and anything in `examples`
+fn example_files(day: &Path) -> Vec<PathBuf> {
+    let old_style = files_in(day).into_iter().filter(|p| {
+        p.file_name()
+            .and_then(|n| n.to_str())
+            .is_some_and(|n| n.starts_with("example") && n.ends_with(".txt"))
+    });
+    let examples = files_in(&day.join("examples"))
+        .into_iter()
+        .filter(|p| p.extension().is_some_and(|e| e == "txt"));
+
+    let mut files = old_style.chain(examples).collect::<Vec<_>>();
+    files.sort();
+    files
+}
+
+fn day_number(day: &Path) -> Option<u8> {
+    day.file_name()?
+        .to_str()?
+        .strip_prefix("day_")?
+        .parse()
+        .ok()
+}
+
+/// Writes sorted day numbers as ranges, `1-3, 5`
+fn ranges(numbers: &[u8]) -> String {
+    let mut ranges: Vec<(u8, u8)> = Vec::new();
+
+    for &n in numbers {
+        match ranges.last_mut() {
+            Some((_, end)) if *end + 1 == n => *end = n,
+            _ => ranges.push((n, n)),
+        }
+    }
+
+    ranges
+        .iter()
+        .map(|&(start, end)| match start == end {
+            true => start.to_string(),
+            false => format!("{start}-{end}"),
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn subfolders(path: &Path) -> Result<Vec<PathBuf>, BuildError> {
+    let mut folders = fs::read_dir(path)
+        .map_err(|e| BuildError::io(path, e))?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|p| p.is_dir())
+        .collect::<Vec<_>>();
+    folders.sort();
+
+    Ok(folders)
+}
+
+/// The files directly in the folder, none when it is missing
+fn files_in(path: &Path) -> Vec<PathBuf> {
+    fs::read_dir(path)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok().map(|e| e.path()))
+                .filter(|p| p.is_file())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ranges() {
+        assert_eq!(ranges(&[]), "");
+        assert_eq!(ranges(&[1, 2, 3, 5, 7, 8]), "1-3, 5, 7-8");
+    }
+}
@@ -1,103 +1,538 @@
+use std::collections::BTreeMap;
 use std::fs;
-use std::io::{Read, Write};
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::time::Duration;
 
-use clap::Parser;
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use similar::TextDiff;
+
+mod error;
+mod fetch;
+mod index;
+mod list;
+mod migrate;
+mod puzzle;
+mod scaffold;
+mod template;
+
+use error::BuildError;
+use puzzle::Puzzle;
+use scaffold::Scaffold;
+use template::Context;
 
 #[derive(Parser, Debug)]
-#[command(author, version, about, long_about = None)]
-struct Args {
-    /// Output directory
-    #[arg(short, long, required = true)]
+#[command(
+    author,
+    version,
+    about,
+    long_about = None,
+    args_conflicts_with_subcommands = true,
+    arg_required_else_help = true
+)]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    #[command(flatten)]
+    new: Option<NewArgs>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Rewrites existing days' Cargo.toml and tests to match the template,
+    /// leaving the solution alone
+    Migrate {
+        /// Day folders, or year folders to migrate every day in
+        #[arg(required = true)]
+        paths: Vec<PathBuf>,
+
+        /// Source (data) directory
+        #[arg(short, long, default_value = "data")]
+        source_dir: String,
+
+        /// Which template the days should match
+        #[arg(short, long, value_enum, default_value_t = Kind::Lines)]
+        kind: Kind,
+    },
+    /// Downloads a day's input into its input.txt, or copies it from the cache
+    Fetch {
+        /// Folder holding the year folders
+        #[arg(short, long, default_value = ".")]
+        root: PathBuf,
+
+        #[arg(short, long)]
+        year: u16,
+
+        #[arg(short, long)]
+        day: u8,
+
+        /// The session cookie from a logged in browser
+        #[arg(long, env = "AOC_SESSION", hide_env_values = true)]
+        session: Option<String>,
+
+        #[arg(long, env = "AOC_BASE_URL", default_value = "https://adventofcode.com")]
+        base_url: String,
+
+        /// Where downloaded inputs are kept so they are never downloaded twice
+        #[arg(long, env = "AOC_CACHE_DIR", default_value_os_t = fetch::default_cache_dir())]
+        cache_dir: PathBuf,
+
+        /// Seconds to leave between downloads
+        #[arg(long, default_value_t = 5)]
+        throttle: u64,
+
+        /// Replace an input.txt that is not empty
+        #[arg(short, long)]
+        force: bool,
+    },
+    /// Lists the days in each year folder and points out the ones that still
+    /// need work
+    List {
+        /// Folder holding the year folders
+        #[arg(short, long, default_value = ".")]
+        root: PathBuf,
+    },
+}
+
+// Creates a new day, this is what runs when no subcommand is given
+#[derive(Args, Debug)]
+struct NewArgs {
+    /// Output directory, the year folder is created inside it when a year is given
+    #[arg(short, long, default_value = ".")]
     out_dir: String,
 
     /// Source (data) directory
     #[arg(short, long, required = true, default_value = "data")]
     source_dir: String,
 
+    /// Year, creates the year workspace if it is missing
+    #[arg(short, long)]
+    year: Option<u16>,
+
     /// Day number
     #[arg(short, long, required = true)]
     day: u8,
+
+    /// Which template to start the day from
+    #[arg(short, long, value_enum, default_value_t = Kind::Lines)]
+    kind: Kind,
+
+    /// Title for the readme, defaults to the puzzle page's or "Day N"
+    #[arg(short, long)]
+    title: Option<String>,
+
+    /// A puzzle page saved from the browser, fills in the readme, the example
+    /// and the year's answers.toml from it
+    #[arg(long)]
+    from_html: Option<PathBuf>,
+
+    /// Overwrite an existing day, its solution and input.txt are backed up first
+    #[arg(short, long)]
+    force: bool,
+
+    /// Print the files that would be created and their contents, writing nothing
+    #[arg(long)]
+    dry_run: bool,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum Kind {
+    /// Splits the input into a Vec<String>
+    Lines,
+    /// Parses the input into a 2D map
+    Grid,
+    /// Parses each line with nom and the parsers in aoc_common
+    Nom,
+    /// Runs the input as a program on a small computer
+    Vm,
+}
+
+/// Files every kind of day starts with, unless the kind has its own
+const COMMON_DIR: &str = "common";
+
+impl Kind {
+    fn template_dir(&self) -> &'static str {
+        match self {
+            Kind::Lines => "lines",
+            Kind::Grid => "grid",
+            Kind::Nom => "nom",
+            Kind::Vm => "vm",
+        }
+    }
+
+    /// The shared files and then the kind's own, which win over the shared ones
+    fn template_dirs(&self, source_dir: &Path) -> Vec<PathBuf> {
+        vec![
+            source_dir.join(COMMON_DIR),
+            source_dir.join(self.template_dir()),
+        ]
+    }
 }
 
-fn main() -> std::io::Result<()> {
-    let args = Args::parse();
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+
+    let result = match (cli.command, cli.new) {
+        (
+            Some(Command::Migrate {
+                paths,
+                source_dir,
+                kind,
+            }),
+            _,
+        ) => migrate::migrate(&paths, &kind.template_dirs(Path::new(&source_dir))),
+        (
+            Some(Command::Fetch {
+                root,
+                year,
+                day,
+                session,
+                base_url,
+                cache_dir,
+                throttle,
+                force,
+            }),
+            _,
+        ) => fetch::fetch(&fetch::Fetch {
+            root: &root,
+            year,
+            day,
+            session: session.as_deref(),
+            base_url: &base_url,
+            cache_dir: &cache_dir,
+            throttle: Duration::from_secs(throttle),
+            force,
+        }),
+        (Some(Command::List { root }), _) => list::list(&root),
+        (None, Some(args)) => build(args),
+        (None, None) => unreachable!("clap shows the help without arguments"),
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("Error: {err}");
+            err.exit_code()
+        }
+    }
+}
 
+fn build(args: NewArgs) -> Result<(), BuildError> {
     let day = args.day;
-    let out_folder = args.out_dir;
-    let data_folder = args.source_dir;
+    let data_folder = PathBuf::from(args.source_dir);
+
+    let mut scaffold = if args.dry_run {
+        Scaffold::dry_run()
+    } else {
+        Scaffold::new()
+    };
+
+    let out_folder = match args.year {
+        Some(year) => PathBuf::from(&args.out_dir).join(year.to_string()),
+        None => PathBuf::from(&args.out_dir),
+    };
+    let year = args.year.or_else(|| year_from_folder(&out_folder));
+
+    let puzzle = match &args.from_html {
+        Some(path) => {
+            puzzle::parse(&fs::read_to_string(path).map_err(|e| BuildError::io(path, e))?)
+        }
+        None => Puzzle::default(),
+    };
+    let title = args
+        .title
+        .or(puzzle.title)
+        .unwrap_or_else(|| format!("Day {day}"));
+    let part = |n: usize| puzzle.parts.get(n).cloned().unwrap_or_default();
 
-    let template_path = format!("{data_folder}/day_0x");
-    let out_dir = format!("{out_folder}/day_{:02}", day);
-    let src_dir = "src";
-    let test_dir = "tests";
+    let mut context = Context::new();
+    context
+        .set("day", day.to_string())
+        .set("day_padded", format!("{:02}", day))
+        .set("crate_name", format!("day_{:02}", day))
+        .set("title", title)
+        .set("year", year.map(|y| y.to_string()).unwrap_or_default())
+        .set("part_1", part(0))
+        .set("part_2", part(1));
 
-    if fs::metadata(&out_dir).is_ok() {
-        panic!("Day already exists");
+    if let Some(year) = args.year {
+        let created = create_year(&mut scaffold, &out_folder, &data_folder, &context)?;
+        if created > 0 && !args.dry_run {
+            println!("Year {year} is ready in {}", out_folder.display());
+        }
     }
 
-    fs::create_dir_all(&format!("{out_dir}/{src_dir}")).expect("Unable to create directories");
-    fs::create_dir_all(&format!("{out_dir}/{test_dir}")).expect("Unable to create directories");
+    let out_dir = out_folder.join(format!("day_{:02}", day));
 
-    create_file(&out_dir, &template_path, "src/main.rs", None);
+    if out_dir.exists() {
+        if !args.force {
+            return Err(BuildError::DayExists(out_dir));
+        }
 
-    let day_replace_fn =
-        move |text: &String| -> String { text.replace("day_0x", &format!("day_{:02}", day)) };
-    create_file(
-        &out_dir,
-        &template_path,
-        "tests/test_example.rs",
-        Some(&day_replace_fn),
-    );
+        for file_name in ["src/main.rs", "src/lib.rs", "input.txt"] {
+            let backup = scaffold.backup_file(&out_dir.join(file_name))?;
+            if let (Some(backup), false) = (backup, args.dry_run) {
+                println!("Backed up {file_name} to {}", backup.display());
+            }
+        }
+    }
 
-    create_file(&out_dir, &template_path, "example.txt", None);
-    create_file(&out_dir, &template_path, "input.txt", None);
-    create_file(
+    render_dir(
+        &mut scaffold,
+        &args.kind.template_dirs(&data_folder),
         &out_dir,
-        &template_path,
-        "Cargo.toml",
-        Some(&day_replace_fn),
-    );
+        &context,
+        true,
+    )?;
 
-    let readme_day_replace_fn =
-        move |text: &String| -> String { text.replace("Day X", &format!("Day {}", day)) };
-    create_file(
-        &out_dir,
-        &template_path,
-        "readme.md",
-        Some(&readme_day_replace_fn),
-    );
+    if let Some(example) = &puzzle.example {
+        scaffold.write_file(&out_dir.join("examples/example.txt"), example)?;
+    }
+    record_answers(&mut scaffold, &out_folder, day, &puzzle.answers)?;
+
+    let title = context.get("title").unwrap_or_default();
+    update_indexes(&mut scaffold, &out_folder, day, title, year)?;
+
+    if args.dry_run {
+        scaffold.preview(Path::new(&args.out_dir));
+        return Ok(());
+    }
 
-    println!("Created day {:02} in {}", day, out_dir);
+    scaffold.commit();
+
+    println!("Created day {:02} in {}", day, out_dir.display());
     println!("Good luck finding the solution!");
 
     Ok(())
 }
 
-type TextReplaceFn = dyn Fn(&String) -> String;
+/// Creates the year workspace, only filling in the files that are missing so a
+/// year folder made by hand still ends up with a workspace. Gives how many
+/// files were created.
+fn create_year(
+    scaffold: &mut Scaffold,
+    out_dir: &Path,
+    data_folder: &Path,
+    context: &Context,
+) -> Result<usize, BuildError> {
+    render_dir(
+        scaffold,
+        &[data_folder.join("20xx")],
+        out_dir,
+        context,
+        false,
+    )
+}
 
-fn create_file(
-    out_dir: &str,
-    template_dir: &str,
-    file_name: &str,
-    replace_fn: Option<&TextReplaceFn>,
-) {
-    let out_path = Path::new(&out_dir);
-
-    let mut main_template = String::new();
-    fs::File::open(&format!("{template_dir}/{file_name}"))
-        .expect("Unable to open file")
-        .read_to_string(&mut main_template)
-        .expect("Unable to read file");
-
-    let main_path = out_path.join(file_name);
-
-    let processed = if replace_fn.is_some() {
-        replace_fn.unwrap()(&main_template)
-    } else {
-        main_template
+/// Links the new day from the year readme and workspace, and the year from the
+/// root readme. Only files that already exist are touched.
+fn update_indexes(
+    scaffold: &mut Scaffold,
+    year_folder: &Path,
+    day: u8,
+    title: &str,
+    year: Option<u16>,
+) -> Result<(), BuildError> {
+    let crate_name = format!("day_{:02}", day);
+
+    update_file(scaffold, &year_folder.join("readme.md"), |text| {
+        index::add_day_link(text, day, title)
+    })?;
+    update_file(scaffold, &year_folder.join("Cargo.toml"), |text| {
+        index::add_workspace_member(text, &crate_name)
+    })?;
+
+    if let (Some(year), Some(root)) = (year, root_folder(year_folder)) {
+        update_file(scaffold, &root.join("readme.md"), |text| {
+            index::add_year_link(text, year)
+        })?;
+    }
+
+    Ok(())
+}
+
+/// Adds the answers from the puzzle page to the year's answers.toml, starting
+/// the file when the year does not have one yet
+fn record_answers(
+    scaffold: &mut Scaffold,
+    year_folder: &Path,
+    day: u8,
+    answers: &[String],
+) -> Result<(), BuildError> {
+    let path = year_folder.join("answers.toml");
+    let exists = scaffold.exists(&path);
+    let text = match exists {
+        true => scaffold.read_to_string(&path)?,
+        false => String::new(),
     };
 
-    let mut output_file = fs::File::create(main_path).expect("Unable to create file");
-    write!(output_file, "{}", processed).expect("Unable to write file");
+    match (index::add_answers(&text, day, answers), exists) {
+        (Some(updated), true) => scaffold.update_file(&path, &updated),
+        (Some(updated), false) => scaffold.write_file(&path, &updated),
+        (None, _) => Ok(()),
+    }
+}
+
+/// The folder holding the year folder, found even when the year folder is not
+/// there yet because this is a dry run
+fn root_folder(year_folder: &Path) -> Option<PathBuf> {
+    if let Ok(year_folder) = fs::canonicalize(year_folder) {
+        return year_folder.parent().map(Path::to_path_buf);
+    }
+
+    let parent = year_folder
+        .parent()
+        .filter(|p| !p.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+    fs::canonicalize(parent).ok()
+}
+
+fn update_file(
+    scaffold: &mut Scaffold,
+    path: &Path,
+    update: impl Fn(&str) -> Option<String>,
+) -> Result<(), BuildError> {
+    if !scaffold.exists(path) {
+        return Ok(());
+    }
+
+    let text = scaffold.read_to_string(path)?;
+
+    if let Some(updated) = update(&text) {
+        scaffold.update_file(path, &updated)?;
+        if !scaffold.is_dry_run() {
+            println!("Updated {}", path.display());
+        }
+    }
+
+    Ok(())
+}
+
+/// Renders every file under the template directories into the output
+/// directory, keeping the same layout. A file in more than one directory comes
+/// from the last. Existing files are only replaced when `overwrite` is set.
+/// Gives how many files were written.
+fn render_dir(
+    scaffold: &mut Scaffold,
+    template_dirs: &[PathBuf],
+    out_dir: &Path,
+    context: &Context,
+    overwrite: bool,
+) -> Result<usize, BuildError> {
+    let mut files = BTreeMap::new();
+    for template_dir in template_dirs {
+        for relative in template_files(template_dir)? {
+            files.insert(relative.clone(), template_dir.join(relative));
+        }
+    }
+
+    scaffold.create_dir(out_dir)?;
+    let mut written = 0;
+
+    for (relative, template_file) in files {
+        let out_path = out_dir.join(&relative);
+        if scaffold.exists(&out_path) && !overwrite {
+            continue;
+        }
+
+        create_file(scaffold, &template_file, &out_path, context)?;
+        written += 1;
+    }
+
+    Ok(written)
+}
+
+/// Where the file comes from in the last template directory that has it
+fn template_file(template_dirs: &[PathBuf], relative: &str) -> PathBuf {
+    template_dirs
+        .iter()
+        .rev()
+        .map(|dir| dir.join(relative))
+        .find(|path| path.exists())
+        .unwrap_or_else(|| {
+            template_dirs
+                .last()
+                .expect("A template directory")
+                .join(relative)
+        })
+}
+
+/// Lists every file in the template, relative to it and in a stable order
+fn template_files(template_dir: &Path) -> Result<Vec<PathBuf>, BuildError> {
+    let mut files = Vec::new();
+    let mut pending = vec![PathBuf::new()];
+
+    while let Some(relative) = pending.pop() {
+        let dir = template_dir.join(&relative);
+        let entries = fs::read_dir(&dir).map_err(|source| BuildError::TemplateMissing {
+            path: dir.clone(),
+            source,
+        })?;
+
+        for entry in entries {
+            let entry = entry.map_err(|e| BuildError::io(&dir, e))?;
+            let path = relative.join(entry.file_name());
+
+            if entry.path().is_dir() {
+                pending.push(path);
+            } else {
+                files.push(path);
+            }
+        }
+    }
+
+    files.sort();
+
+    Ok(files)
+}
+
+fn create_file(
+    scaffold: &mut Scaffold,
+    template_file: &Path,
+    out_path: &Path,
+    context: &Context,
+) -> Result<(), BuildError> {
+    let processed = render_file(template_file, context)?;
+
+    if let Some(parent) = out_path.parent() {
+        scaffold.create_dir(parent)?;
+    }
+
+    scaffold.write_file(out_path, &processed)
+}
+
+fn render_file(template_file: &Path, context: &Context) -> Result<String, BuildError> {
+    let text = fs::read_to_string(template_file).map_err(|source| BuildError::TemplateMissing {
+        path: template_file.to_path_buf(),
+        source,
+    })?;
+
+    template::render(&text, context).map_err(|source| BuildError::Template {
+        path: template_file.to_path_buf(),
+        source,
+    })
+}
+
+pub fn print_diff(path: &Path, old: &str, new: &str) {
+    let name = path.display().to_string();
+    let diff = TextDiff::from_lines(old, new);
+
+    print!(
+        "{}",
+        diff.unified_diff()
+            .context_radius(2)
+            .header(&name, &name)
+            .missing_newline_hint(false)
+    );
+}
+
+/// Works out the year when the output directory is already a year folder
+fn year_from_folder(folder: &Path) -> Option<u16> {
+    fs::canonicalize(folder)
+        .unwrap_or_else(|_| folder.to_path_buf())
+        .file_name()?
+        .to_str()?
+        .parse()
+        .ok()
 }
@@ -0,0 +1,460 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::error::BuildError;
+use crate::print_diff;
+use crate::scaffold::Scaffold;
+use crate::template::Context;
+
+const TEST_FILE: &str = "tests/test_example.rs";
+const EXAMPLES_DIR: &str = "examples";
+
+/// One example pulled out of an old style test, the file fed to stdin and
+/// what the test expected on stdout
+#[derive(Debug, PartialEq)]
+struct Example {
+    input_file: String,
+    expected: String,
+}
+
+/// Brings each day folder up to date with the template, leaving the solution
+/// alone. Year folders are expanded to every day in them.
+pub fn migrate(paths: &[PathBuf], template_dirs: &[PathBuf]) -> Result<(), BuildError> {
+    let mut days = Vec::new();
+
+    for path in paths {
+        if is_crate(path) {
+            days.push(path.clone());
+            continue;
+        }
+
+        let mut year_days = fs::read_dir(path)
+            .map_err(|e| BuildError::io(path, e))?
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|p| is_day_folder(p))
+            .collect::<Vec<_>>();
+        year_days.sort();
+
+        if path.join("day_0x").exists() {
+            println!(
+                "Skipping {}, the template in template/data replaces it",
+                path.join("day_0x").display()
+            );
+        }
+
+        days.extend(year_days);
+    }
+
+    for day in days {
+        migrate_day(&day, template_dirs)?;
+    }
+
+    Ok(())
+}
+
+fn is_crate(path: &Path) -> bool {
+    fs::read_to_string(path.join("Cargo.toml")).is_ok_and(|c| c.contains("[package]"))
+}
+
+pub fn is_day_folder(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|n| n.to_str())
+        .and_then(|n| n.strip_prefix("day_"))
+        .is_some_and(|n| n.parse::<u8>().is_ok())
+        && is_crate(path)
+}
+
+fn migrate_day(day_dir: &Path, template_dirs: &[PathBuf]) -> Result<(), BuildError> {
+    println!("== {}", day_dir.display());
+
+    let cargo_path = day_dir.join("Cargo.toml");
+    let old_cargo = fs::read_to_string(&cargo_path).map_err(|e| BuildError::io(&cargo_path, e))?;
+
+    let context = day_context(day_dir, &old_cargo);
+    let mut scaffold = Scaffold::new();
+    let mut changed = false;
+
+    let template_cargo =
+        crate::render_file(&crate::template_file(template_dirs, "Cargo.toml"), &context)?;
+    let new_cargo = merge_cargo_toml(&old_cargo, &template_cargo);
+    if new_cargo != old_cargo {
+        print_diff(&cargo_path, &old_cargo, &new_cargo);
+        scaffold.update_file(&cargo_path, &new_cargo)?;
+        changed = true;
+    }
+
+    let test_path = day_dir.join(TEST_FILE);
+    let old_test = fs::read_to_string(&test_path).unwrap_or_default();
+    let new_test = crate::render_file(&crate::template_file(template_dirs, TEST_FILE), &context)?;
+
+    let mut tests_migrated = old_test == new_test;
+
+    if !tests_migrated {
+        match extract_examples(&old_test) {
+            Ok(examples) => {
+                move_examples(&mut scaffold, day_dir, &examples)?;
+                print_diff(&test_path, &old_test, &new_test);
+                if let Some(parent) = test_path.parent() {
+                    scaffold.create_dir(parent)?;
+                }
+                scaffold.write_file(&test_path, &new_test)?;
+                changed = true;
+                tests_migrated = true;
+            }
+            Err(reason) => println!("Keeping {}, {reason}", test_path.display()),
+        }
+    }
+
+    report_leftovers(day_dir, tests_migrated);
+
+    scaffold.commit();
+
+    if !changed {
+        println!("Already up to date");
+    }
+
+    Ok(())
+}
+
+fn day_context(day_dir: &Path, cargo_toml: &str) -> Context {
+    let crate_name = cargo_toml
+        .lines()
+        .find_map(|l| {
+            let (key, value) = l.split_once('=')?;
+            (key.trim() == "name").then(|| value.trim().trim_matches('"').to_string())
+        })
+        .unwrap_or_default();
+    let day = crate_name
+        .strip_prefix("day_")
+        .and_then(|d| d.parse::<u8>().ok())
+        .unwrap_or_default();
+    let year = fs::canonicalize(day_dir)
+        .ok()
+        .and_then(|p| p.parent()?.file_name()?.to_str()?.parse::<u16>().ok())
+        .map(|y| y.to_string())
+        .unwrap_or_default();
+
+    let mut context = Context::new();
+    context
+        .set("day", day.to_string())
+        .set("day_padded", format!("{:02}", day))
+        .set("crate_name", crate_name)
+        .set("title", format!("Day {day}"))
+        .set("year", year);
+    context
+}
+
+fn move_examples(
+    scaffold: &mut Scaffold,
+    day_dir: &Path,
+    examples: &[Example],
+) -> Result<(), BuildError> {
+    let examples_dir = day_dir.join(EXAMPLES_DIR);
+    scaffold.create_dir(&examples_dir)?;
+
+    for example in examples {
+        let from = day_dir.join(&example.input_file);
+        let stem = Path::new(&example.input_file)
+            .file_stem()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_else(|| "example".to_string());
+        let to = examples_dir.join(format!("{stem}.txt"));
+        let expected = examples_dir.join(format!("{stem}.expected"));
+
+        scaffold.rename_file(&from, &to)?;
+        println!("Moved {} to {}", from.display(), to.display());
+
+        print_diff(&expected, "", &example.expected);
+        scaffold.write_file(&expected, &example.expected)?;
+    }
+
+    Ok(())
+}
+
+/// Points out files the migration can't sort out by itself. Example files are
+/// only left over once the old test that might read them has gone.
+fn report_leftovers(day_dir: &Path, tests_migrated: bool) {
+    let Ok(entries) = fs::read_dir(day_dir) else {
+        return;
+    };
+
+    let mut leftovers = entries
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|p| p.is_file())
+        .collect::<Vec<_>>();
+    leftovers.sort();
+
+    for path in leftovers {
+        let name = path.file_name().unwrap_or_default().to_string_lossy();
+
+        if name.ends_with(".rs") {
+            println!(
+                "Warning: {} is outside src and is not built",
+                path.display()
+            );
+        } else if tests_migrated && name.starts_with("example") && name.ends_with(".txt") {
+            println!(
+                "Warning: {} is not tested, move it to {EXAMPLES_DIR}/ with a .expected file",
+                path.display()
+            );
+        }
+    }
+}
+
+/// Says a day is built on `Solution`, which an older day is not until it is
+/// ported by hand
+const SOLUTION_HEADER: &str = "[package.metadata.aoc]";
+
+/// Takes the template's Cargo.toml and puts back anything the day added. The
+/// day's own dependencies win, the template's dev-dependencies win so every
+/// day tests with the same pins. The template's `Solution` marker is only kept
+/// for a day that already had it.
+fn merge_cargo_toml(old: &str, template: &str) -> String {
+    let old_sections = sections(old);
+    let mut merged = sections(template);
+
+    if !old_sections.iter().any(|(h, _)| h == SOLUTION_HEADER) {
+        merged.retain(|(h, _)| h != SOLUTION_HEADER);
+        if let Some((_, lines)) = merged.last_mut() {
+            while lines.last().is_some_and(|l| l.trim().is_empty()) {
+                lines.pop();
+            }
+        }
+    }
+
+    for (header, lines) in &old_sections {
+        let Some((_, merged_lines)) = merged.iter_mut().find(|(h, _)| h == header) else {
+            if !header.is_empty() {
+                merged.push((header.clone(), lines.clone()));
+            }
+            continue;
+        };
+
+        if header != "[dependencies]" && header != "[dev-dependencies]" {
+            continue;
+        }
+        let day_wins = header == "[dependencies]";
+
+        for line in lines {
+            let Some(name) = dependency_name(line) else {
+                continue;
+            };
+
+            match merged_lines
+                .iter()
+                .position(|l| dependency_name(l) == Some(name))
+            {
+                Some(ix) if day_wins => merged_lines[ix] = line.clone(),
+                Some(_) => {}
+                None => {
+                    let insert_at = merged_lines
+                        .iter()
+                        .rposition(|l| !l.trim().is_empty())
+                        .map_or(0, |ix| ix + 1);
+                    merged_lines.insert(insert_at, line.clone());
+                }
+            }
+        }
+    }
+
+    let mut text = merged
+        .iter()
+        .flat_map(|(header, lines)| {
+            let header = (!header.is_empty()).then_some(header);
+            header.into_iter().chain(lines)
+        })
+        .cloned()
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    if template.ends_with('\n') {
+        text.push('\n');
+    }
+
+    text
+}
+
+/// Splits a toml file into its `[section]` headers and their lines, anything
+/// before the first header goes under an empty header
+fn sections(text: &str) -> Vec<(String, Vec<String>)> {
+    let mut sections = vec![(String::new(), Vec::new())];
+
+    for line in text.lines() {
+        if line.trim_start().starts_with('[') {
+            sections.push((line.trim().to_string(), Vec::new()));
+        } else {
+            sections
+                .last_mut()
+                .expect("There is always a section")
+                .1
+                .push(line.to_string());
+        }
+    }
+
+    sections
+}
+
+fn dependency_name(line: &str) -> Option<&str> {
+    let (name, _) = line.split_once('=')?;
+    let name = name.trim();
+
+    (!name.is_empty() && !name.starts_with('#')).then_some(name)
+}
+
+/// Pulls the examples out of an old style test, one per `#[test]` function.
+/// Anything the migration can't be sure about is an error so the test is kept.
+fn extract_examples(test: &str) -> Result<Vec<Example>, String> {
+    let tests = test.split("#[test]").skip(1).collect::<Vec<_>>();
+
+    if tests.is_empty() {
+        return Err("there are no tests to migrate".to_string());
+    }
+
+    tests
+        .into_iter()
+        .map(|body| {
+            let input_file = ["push(\"", "read_file(\""]
+                .iter()
+                .find_map(|prefix| {
+                    let start = body.find(prefix)? + prefix.len();
+                    let end = start + body[start..].find('"')?;
+                    Some(body[start..end].to_string())
+                })
+                .ok_or("a test doesn't read an example file")?;
+
+            let start = body
+                .find(".stdout(")
+                .ok_or(format!("the test for {input_file} doesn't check stdout"))?
+                + ".stdout(".len();
+            let expected = parse_string_literal(body[start..].trim_start()).ok_or(format!(
+                "the output expected for {input_file} is not a plain string"
+            ))?;
+
+            Ok(Example {
+                input_file,
+                expected,
+            })
+        })
+        .collect()
+}
+
+/// Reads a Rust string literal from the start of the text, followed by the
+/// closing bracket of the call it is in
+fn parse_string_literal(text: &str) -> Option<String> {
+    let mut chars = text.strip_prefix('"')?.chars();
+    let mut value = String::new();
+
+    loop {
+        match chars.next()? {
+            '"' => break,
+            '\\' => match chars.next()? {
+                'n' => value.push('\n'),
+                't' => value.push('\t'),
+                'r' => value.push('\r'),
+                '0' => value.push('\0'),
+                '\\' => value.push('\\'),
+                '"' => value.push('"'),
+                '\'' => value.push('\''),
+                '\n' => {
+                    let rest = chars.as_str().trim_start();
+                    chars = rest.chars();
+                }
+                _ => return None,
+            },
+            c => value.push(c),
+        }
+    }
+
+    let rest = chars.as_str().trim_start();
+    let rest = rest.strip_prefix(',').unwrap_or(rest).trim_start();
+
+    rest.starts_with(')').then_some(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OLD_CARGO: &str = "[package]\nname = \"day_04\"\nversion = \"0.1.0\"\nedition = \"2021\"\n\n[dependencies]\nitertools=\"0.9.0\"\n\n[dev-dependencies]\nassert_cmd = \"2.0.2\"\nrstest = \"0.16.0\"";
+    const TEMPLATE_CARGO: &str = "[package]\nname = \"day_04\"\nversion = \"0.1.0\"\nedition = \"2021\"\n\n[dependencies]\n\n[dev-dependencies]\nassert_cmd = \"2.1.1\"";
+
+    #[test]
+    fn test_merge_cargo_toml() {
+        assert_eq!(
+            merge_cargo_toml(OLD_CARGO, TEMPLATE_CARGO),
+            "[package]\nname = \"day_04\"\nversion = \"0.1.0\"\nedition = \"2021\"\n\n[dependencies]\nitertools=\"0.9.0\"\n\n[dev-dependencies]\nassert_cmd = \"2.1.1\"\nrstest = \"0.16.0\""
+        );
+    }
+
+    #[test]
+    fn test_merge_leaves_out_solution_marker() {
+        let template = format!("{TEMPLATE_CARGO}\n\n[package.metadata.aoc]\nsolution = true\n");
+
+        assert_eq!(
+            merge_cargo_toml(OLD_CARGO, &template),
+            format!("{}\n", merge_cargo_toml(OLD_CARGO, TEMPLATE_CARGO))
+        );
+
+        let ported = format!("{OLD_CARGO}\n\n[package.metadata.aoc]\nsolution = true");
+        assert!(merge_cargo_toml(&ported, &template)
+            .ends_with("\n[package.metadata.aoc]\nsolution = true\n"));
+    }
+
+    #[test]
+    fn test_merge_is_stable() {
+        let merged = merge_cargo_toml(OLD_CARGO, TEMPLATE_CARGO);
+
+        assert_eq!(merge_cargo_toml(&merged, TEMPLATE_CARGO), merged);
+    }
+
+    #[test]
+    fn test_extract_examples() {
+        let test = r#"
+#[test]
+fn test_example() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("day_01")?;
+    file_path.push("example.txt");
+    let assert = cmd.write_stdin(contents).assert();
+    assert.stdout(
+        "Part 1 is 142\nPart 2 is \"142\"\n",
+    );
+}
+
+#[test]
+fn test_example2() -> Result<(), Box<dyn std::error::Error>> {
+    file_path.push("example2.txt");
+    cmd.write_stdin(contents)
+        .assert()
+        .stdout("Part 1 is \
+                 209\n");
+}
+"#;
+
+        assert_eq!(
+            extract_examples(test),
+            Ok(vec![
+                Example {
+                    input_file: "example.txt".to_string(),
+                    expected: "Part 1 is 142\nPart 2 is \"142\"\n".to_string()
+                },
+                Example {
+                    input_file: "example2.txt".to_string(),
+                    expected: "Part 1 is 209\n".to_string()
+                }
+            ])
+        );
+    }
+
+    #[test]
+    fn test_extract_examples_refuses_formatted_output() {
+        let test = r#"
+#[test]
+fn test_example() {
+    let input = read_file("example.txt");
+    assert.stdout(format!("Signal is {}\n", output));
+}
+"#;
+
+        assert!(extract_examples(test).is_err());
+    }
+}
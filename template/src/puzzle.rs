@@ -0,0 +1,170 @@
+use scraper::{ElementRef, Html, Node, Selector};
+
+/// The parts of a saved puzzle page that are worth keeping in the day folder
+#[derive(Debug, Default, PartialEq)]
+pub struct Puzzle {
+    /// The heading of the first part without the dashes, `Day 12: Garden Groups`
+    pub title: Option<String>,
+    /// Each part's description as Markdown, part two only once it is unlocked
+    pub parts: Vec<String>,
+    /// The first `<pre><code>` block on the page
+    pub example: Option<String>,
+    /// Anything after "Your puzzle answer was", one per solved part
+    pub answers: Vec<String>,
+}
+
+/// Pulls the title, descriptions, example and answers out of a puzzle page
+/// saved from the browser. Anything missing from the page is left empty.
+pub fn parse(html: &str) -> Puzzle {
+    let document = Html::parse_document(html);
+    let articles = selector("article.day-desc");
+    let heading = selector("h2");
+    let example = selector("pre > code");
+    let paragraphs = selector("main > p, body > p");
+
+    let mut puzzle = Puzzle::default();
+
+    for article in document.select(&articles) {
+        if puzzle.title.is_none() {
+            puzzle.title = article
+                .select(&heading)
+                .next()
+                .map(|h| text(h).trim_matches(|c| c == '-' || c == ' ').to_string());
+        }
+        if puzzle.example.is_none() {
+            puzzle.example = article.select(&example).next().map(text);
+        }
+        puzzle.parts.push(to_markdown(article));
+    }
+
+    puzzle.answers = document
+        .select(&paragraphs)
+        .filter(|p| text(*p).starts_with("Your puzzle answer was"))
+        .filter_map(|p| p.select(&selector("code")).next().map(text))
+        .collect();
+
+    puzzle
+}
+
+fn selector(css: &str) -> Selector {
+    Selector::parse(css).expect("Selectors are written by hand")
+}
+
+fn text(element: ElementRef) -> String {
+    element.text().collect()
+}
+
+/// Converts the blocks in an article to Markdown, the heading is left out as
+/// the readme has its own
+fn to_markdown(article: ElementRef) -> String {
+    let mut blocks = Vec::new();
+
+    for child in article.children().filter_map(ElementRef::wrap) {
+        match child.value().name() {
+            "h2" => {}
+            "pre" => blocks.push(format!("```\n{}```", text(child))),
+            "ul" | "ol" => {
+                let items = child
+                    .children()
+                    .filter_map(ElementRef::wrap)
+                    .map(|li| format!("- {}", inline(li, false).trim()))
+                    .collect::<Vec<_>>();
+                blocks.push(items.join("\n"));
+            }
+            _ => blocks.push(inline(child, false).trim().to_string()),
+        }
+    }
+
+    blocks.join("\n\n")
+}
+
+/// Renders the text inside a block, keeping code, emphasis and links. Outside
+/// of code `<`, `>` and `&` are escaped, so Markdown does not take the text
+/// for HTML.
+fn inline(element: ElementRef, in_code: bool) -> String {
+    let mut out = String::new();
+
+    for child in element.children() {
+        match child.value() {
+            Node::Text(text) => {
+                for c in text.chars() {
+                    match c.is_whitespace() {
+                        true if out.ends_with(' ') => {}
+                        true => out.push(' '),
+                        false if in_code => out.push(c),
+                        false => match c {
+                            '<' => out.push_str("&lt;"),
+                            '>' => out.push_str("&gt;"),
+                            '&' => out.push_str("&amp;"),
+                            _ => out.push(c),
+                        },
+                    }
+                }
+            }
+            Node::Element(_) => {
+                let child = ElementRef::wrap(child).expect("The node is an element");
+                let inner = inline(child, in_code || child.value().name() == "code");
+                let inner = match child.value().name() {
+                    "code" if child.select(&selector("em")).next().is_some() => {
+                        format!("**`{}`**", text(child))
+                    }
+                    "code" => format!("`{inner}`"),
+                    "em" => format!("**{inner}**"),
+                    "a" => match child.value().attr("href") {
+                        Some(href) => format!("[{inner}]({href})"),
+                        None => inner,
+                    },
+                    _ => inner,
+                };
+                out.push_str(&inner);
+            }
+            _ => {}
+        }
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAGE: &str = r#"<!DOCTYPE html>
+<html><body><main>
+<article class="day-desc"><h2>--- Day 1: Historian Hysteria ---</h2><p>Pair up the <em>smallest</em> numbers, see <a href="/2024/about">about</a>:</p>
+<pre><code>3   4
+4   3
+</code></pre>
+<ul><li>Add <code>1</code> to it.</li></ul>
+<p>The total is <code><em>11</em></code>.</p>
+</article>
+<p>Your puzzle answer was <code>1530215</code>.</p><article class="day-desc"><h2 id="part2">--- Part Two ---</h2><p>Count the &lt;matches&gt; &amp; <code>a&lt;b</code>.</p>
+</article>
+<p>Your puzzle answer was <code>26800609</code>.</p>
+</main></body></html>"#;
+
+    #[test]
+    fn test_parse() {
+        let puzzle = parse(PAGE);
+
+        assert_eq!(puzzle.title.as_deref(), Some("Day 1: Historian Hysteria"));
+        assert_eq!(puzzle.example.as_deref(), Some("3   4\n4   3\n"));
+        assert_eq!(puzzle.answers, vec!["1530215", "26800609"]);
+        assert_eq!(
+            puzzle.parts,
+            vec![
+                "Pair up the **smallest** numbers, see [about](/2024/about):\n\n```\n3   4\n4   3\n```\n\n- Add `1` to it.\n\nThe total is **`11`**.",
+                "Count the &lt;matches&gt; &amp; `a<b`.",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unsolved_page() {
+        let puzzle = parse("<main><article class=\"day-desc\"><h2>--- Day 3: Mull It Over ---</h2><p>Multiply.</p></article></main>");
+
+        assert_eq!(puzzle.parts, vec!["Multiply."]);
+        assert_eq!(puzzle.example, None);
+        assert!(puzzle.answers.is_empty());
+    }
+}
@@ -0,0 +1,280 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::error::BuildError;
+
+enum Change {
+    Created(PathBuf),
+    BackedUp { original: PathBuf, backup: PathBuf },
+    Modified { path: PathBuf, original: String },
+    Renamed { from: PathBuf, to: PathBuf },
+}
+
+/// What a dry run would have done to a path
+enum Planned {
+    Dir,
+    Created(String),
+    Changed(String),
+    Backup(PathBuf),
+}
+
+/// Keeps track of everything written to disk so a failed build can be undone.
+/// Unless `commit` is called the changes are rolled back when this is dropped.
+///
+/// A dry run scaffold writes nothing, it remembers what it would have written
+/// so `exists` and `read_to_string` still see it and `preview` can show it.
+pub struct Scaffold {
+    changes: Vec<Change>,
+    committed: bool,
+    planned: Option<BTreeMap<PathBuf, Planned>>,
+}
+
+impl Scaffold {
+    pub fn new() -> Self {
+        Scaffold {
+            changes: Vec::new(),
+            committed: false,
+            planned: None,
+        }
+    }
+
+    pub fn dry_run() -> Self {
+        Scaffold {
+            changes: Vec::new(),
+            committed: false,
+            planned: Some(BTreeMap::new()),
+        }
+    }
+
+    pub fn is_dry_run(&self) -> bool {
+        self.planned.is_some()
+    }
+
+    pub fn exists(&self, path: &Path) -> bool {
+        self.planned.as_ref().is_some_and(|p| p.contains_key(path)) || path.exists()
+    }
+
+    pub fn read_to_string(&self, path: &Path) -> Result<String, BuildError> {
+        match self.planned.as_ref().and_then(|p| p.get(path)) {
+            Some(Planned::Created(contents) | Planned::Changed(contents)) => Ok(contents.clone()),
+            Some(Planned::Backup(original)) => self.read_to_string(original),
+            _ => fs::read_to_string(path).map_err(|e| BuildError::io(path, e)),
+        }
+    }
+
+    /// Creates the directory and any missing parents, remembering each one made
+    pub fn create_dir(&mut self, path: &Path) -> Result<(), BuildError> {
+        let mut missing: Vec<&Path> = path.ancestors().take_while(|p| !self.exists(p)).collect();
+        missing.retain(|p| !p.as_os_str().is_empty());
+
+        for dir in missing.into_iter().rev() {
+            if let Some(planned) = &mut self.planned {
+                planned.insert(dir.to_path_buf(), Planned::Dir);
+                continue;
+            }
+            fs::create_dir(dir).map_err(|e| BuildError::io(dir, e))?;
+            self.changes.push(Change::Created(dir.to_path_buf()));
+        }
+
+        Ok(())
+    }
+
+    /// Writes the file. On rollback a new file is removed and one that was
+    /// overwritten gets its old contents back.
+    pub fn write_file(&mut self, path: &Path, contents: &str) -> Result<(), BuildError> {
+        let existed = self.exists(path);
+
+        if existed && self.is_dry_run() && self.read_to_string(path)? == contents {
+            return Ok(());
+        }
+
+        if let Some(planned) = &mut self.planned {
+            let change = match planned.get(path) {
+                Some(Planned::Created(_)) => Planned::Created(contents.to_string()),
+                _ if existed => Planned::Changed(contents.to_string()),
+                _ => Planned::Created(contents.to_string()),
+            };
+            planned.insert(path.to_path_buf(), change);
+            return Ok(());
+        }
+
+        if existed {
+            return self.update_file(path, contents);
+        }
+
+        fs::write(path, contents).map_err(|e| BuildError::io(path, e))?;
+        self.changes.push(Change::Created(path.to_path_buf()));
+
+        Ok(())
+    }
+
+    /// Replaces the contents of an existing file, restoring them on rollback
+    pub fn update_file(&mut self, path: &Path, contents: &str) -> Result<(), BuildError> {
+        if self.is_dry_run() {
+            return self.write_file(path, contents);
+        }
+
+        let original = fs::read_to_string(path).map_err(|e| BuildError::io(path, e))?;
+
+        fs::write(path, contents).map_err(|e| BuildError::io(path, e))?;
+        self.changes.push(Change::Modified {
+            path: path.to_path_buf(),
+            original,
+        });
+
+        Ok(())
+    }
+
+    pub fn rename_file(&mut self, from: &Path, to: &Path) -> Result<(), BuildError> {
+        if self.is_dry_run() {
+            let contents = self.read_to_string(from)?;
+            return self.write_file(to, &contents);
+        }
+
+        fs::rename(from, to).map_err(|e| BuildError::io(from, e))?;
+        self.changes.push(Change::Renamed {
+            from: from.to_path_buf(),
+            to: to.to_path_buf(),
+        });
+
+        Ok(())
+    }
+
+    /// Copies the file next to itself with a `.bak` suffix, returning where it went
+    pub fn backup_file(&mut self, path: &Path) -> Result<Option<PathBuf>, BuildError> {
+        if !self.exists(path) {
+            return Ok(None);
+        }
+
+        let backup = free_backup_path(path);
+        if let Some(planned) = &mut self.planned {
+            planned.insert(backup.clone(), Planned::Backup(path.to_path_buf()));
+            return Ok(Some(backup));
+        }
+
+        fs::copy(path, &backup).map_err(|e| BuildError::io(&backup, e))?;
+        self.changes.push(Change::BackedUp {
+            original: path.to_path_buf(),
+            backup: backup.clone(),
+        });
+
+        Ok(Some(backup))
+    }
+
+    /// Prints what a dry run would do, as a tree of paths under `root` followed
+    /// by the contents of each new file and a diff of each changed one
+    pub fn preview(&self, root: &Path) {
+        let Some(planned) = &self.planned else {
+            return;
+        };
+
+        let absolute_root = fs::canonicalize(root).unwrap_or_else(|_| root.to_path_buf());
+        let mut tree = BTreeMap::new();
+        let mut outside = Vec::new();
+
+        for (path, change) in planned {
+            let Some(relative) = path
+                .strip_prefix(root)
+                .or_else(|_| path.strip_prefix(&absolute_root))
+                .ok()
+            else {
+                outside.push(path);
+                continue;
+            };
+
+            for ancestor in relative.ancestors().skip(1) {
+                if !ancestor.as_os_str().is_empty() {
+                    tree.entry(ancestor.to_path_buf()).or_insert(None);
+                }
+            }
+            tree.insert(relative.to_path_buf(), Some(change));
+        }
+
+        println!("Would write to {}", root.display());
+        for (relative, change) in &tree {
+            let depth = relative.components().count() - 1;
+            let name = relative.file_name().unwrap_or_default().to_string_lossy();
+            let note = match change {
+                None | Some(Planned::Dir) => "/".to_string(),
+                Some(Planned::Created(_)) => String::new(),
+                Some(Planned::Changed(_)) => " (changed)".to_string(),
+                Some(Planned::Backup(original)) => format!(
+                    " (backup of {})",
+                    original.file_name().unwrap_or_default().to_string_lossy()
+                ),
+            };
+            println!("  {}{name}{note}", "  ".repeat(depth));
+        }
+        for path in outside {
+            println!("Would change {}", path.display());
+        }
+
+        for (path, change) in planned {
+            if matches!(change, Planned::Created(_) | Planned::Changed(_)) {
+                println!();
+            }
+            match change {
+                Planned::Created(contents) => {
+                    println!("==> {} <==", path.display());
+                    print!("{contents}");
+                    if !contents.ends_with('\n') {
+                        println!();
+                    }
+                }
+                Planned::Changed(contents) => {
+                    let original = fs::read_to_string(path).unwrap_or_default();
+                    crate::print_diff(path, &original, contents);
+                }
+                Planned::Dir | Planned::Backup(_) => {}
+            }
+        }
+    }
+
+    pub fn commit(mut self) {
+        self.committed = true;
+    }
+
+    fn rollback(&mut self) {
+        for change in self.changes.drain(..).rev() {
+            let result = match &change {
+                Change::Created(path) if path.is_dir() => fs::remove_dir(path),
+                Change::Created(path) => fs::remove_file(path),
+                Change::BackedUp { original, backup } => fs::rename(backup, original),
+                Change::Modified { path, original } => fs::write(path, original),
+                Change::Renamed { from, to } => fs::rename(to, from),
+            };
+
+            if let Err(e) = result {
+                let path = match &change {
+                    Change::Created(path) | Change::Modified { path, .. } => path,
+                    Change::Renamed { from, .. } => from,
+                    Change::BackedUp { original, .. } => original,
+                };
+                eprintln!("Unable to roll back {}: {e}", path.display());
+            }
+        }
+    }
+}
+
+impl Drop for Scaffold {
+    fn drop(&mut self) {
+        if !self.committed {
+            self.rollback();
+        }
+    }
+}
+
+fn free_backup_path(path: &Path) -> PathBuf {
+    let with_suffix = |suffix: String| {
+        let mut backup = path.as_os_str().to_owned();
+        backup.push(suffix);
+        PathBuf::from(backup)
+    };
+
+    std::iter::once(".bak".to_string())
+        .chain((2..).map(|n| format!(".bak.{n}")))
+        .map(with_suffix)
+        .find(|p| !p.exists())
+        .expect("There is always a free backup name")
+}
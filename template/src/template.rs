@@ -0,0 +1,428 @@
+use std::collections::HashMap;
+use std::fmt;
+
+/// Variables available to a template, a variable that is unset or empty is
+/// false in an `{{#if}}` block
+#[derive(Debug, Default, Clone)]
+pub struct Context {
+    vars: HashMap<String, String>,
+}
+
+impl Context {
+    pub fn new() -> Self {
+        Context::default()
+    }
+
+    pub fn set(&mut self, name: &str, value: impl Into<String>) -> &mut Self {
+        self.vars.insert(name.to_string(), value.into());
+        self
+    }
+
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.vars.get(name).map(String::as_str)
+    }
+
+    fn is_truthy(&self, name: &str) -> bool {
+        self.get(name).is_some_and(|v| !v.is_empty())
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum TemplateError {
+    UnknownVariable { name: String, line: usize },
+    UnclosedTag { line: usize },
+    UnclosedBlock { line: usize },
+    UnexpectedTag { tag: String, line: usize },
+}
+
+impl fmt::Display for TemplateError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TemplateError::UnknownVariable { name, line } => {
+                write!(f, "line {line}: unknown variable `{name}`")
+            }
+            TemplateError::UnclosedTag { line } => write!(f, "line {line}: missing `}}}}`"),
+            TemplateError::UnclosedBlock { line } => {
+                write!(f, "line {line}: `{{{{#if}}}}` without `{{{{/if}}}}`")
+            }
+            TemplateError::UnexpectedTag { tag, line } => {
+                write!(f, "line {line}: unexpected `{{{{{tag}}}}}`")
+            }
+        }
+    }
+}
+
+impl std::error::Error for TemplateError {}
+
+#[derive(Debug, PartialEq)]
+enum Token<'a> {
+    Text(&'a str),
+    Var(&'a str),
+    If {
+        name: &'a str,
+        negate: bool,
+    },
+    Else,
+    /// `{{/if}}`, or `{{/unless}}` when `negate` is set
+    EndIf {
+        negate: bool,
+    },
+}
+
+#[derive(Debug)]
+enum Node<'a> {
+    Text(&'a str),
+    Var(&'a str, usize),
+    If {
+        name: &'a str,
+        negate: bool,
+        then: Vec<Node<'a>>,
+        otherwise: Vec<Node<'a>>,
+    },
+}
+
+/// Renders a template, supporting `{{name}}` substitution and
+/// `{{#if name}}`/`{{#unless name}}` ... `{{else}}` ... `{{/if}}` blocks.
+/// A block tag that sits on a line of its own takes its newline with it.
+/// Braces that do not hold a tag, like the `{{}}` in `format!("{{}}")`, are
+/// left as they are.
+pub fn render(template: &str, context: &Context) -> Result<String, TemplateError> {
+    let tokens = tokenise(template)?;
+    let mut tokens = tokens.into_iter().peekable();
+    let nodes = parse_nodes(&mut tokens, None)?;
+
+    let mut out = String::with_capacity(template.len());
+    render_nodes(&nodes, context, &mut out)?;
+
+    Ok(out)
+}
+
+fn tokenise(template: &str) -> Result<Vec<(Token<'_>, usize)>, TemplateError> {
+    let mut tokens = Vec::new();
+    let mut rest = template;
+    let mut line = 1;
+    // Where to look for the next tag, past any braces that were not one
+    let mut from = 0;
+
+    while let Some(start) = rest[from..].find("{{").map(|s| s + from) {
+        let tag_line = line + rest[..start].matches('\n').count();
+        let Some(end) = rest[start..].find("}}").map(|e| e + start) else {
+            match starts_tag(&rest[start + 2..]) {
+                true => return Err(TemplateError::UnclosedTag { line: tag_line }),
+                false => break,
+            }
+        };
+
+        let Some(token) = parse_tag(rest[start + 2..end].trim()) else {
+            from = start + 2;
+            continue;
+        };
+
+        let mut text = &rest[..start];
+        let mut after = end + 2;
+
+        // Block tags alone on a line should not leave a blank line behind
+        if !matches!(token, Token::Var(_)) {
+            let line_start = text.rfind('\n').map_or(0, |i| i + 1);
+            let following = &rest[after..];
+            let line_end = following.find('\n');
+            let alone_before = text[line_start..].trim().is_empty()
+                && (line_start > 0 || tokens.is_empty() || ends_line(&tokens));
+            let alone_after = following[..line_end.unwrap_or(following.len())]
+                .trim()
+                .is_empty();
+
+            if alone_before && alone_after {
+                text = &text[..line_start];
+                after += line_end.map_or(following.len(), |i| i + 1);
+            }
+        }
+
+        if !text.is_empty() {
+            tokens.push((Token::Text(text), line));
+        }
+        tokens.push((token, tag_line));
+        line += rest[..after].matches('\n').count();
+
+        rest = &rest[after..];
+        from = 0;
+    }
+
+    if !rest.is_empty() {
+        tokens.push((Token::Text(rest), line));
+    }
+
+    Ok(tokens)
+}
+
+/// The token for what is between the braces, or `None` when it is not a tag
+fn parse_tag(tag: &str) -> Option<Token<'_>> {
+    let token = match tag.split_once(' ') {
+        Some(("#if", name)) => Token::If {
+            name: name.trim(),
+            negate: false,
+        },
+        Some(("#unless", name)) => Token::If {
+            name: name.trim(),
+            negate: true,
+        },
+        _ if tag == "else" => Token::Else,
+        _ if tag == "/if" => Token::EndIf { negate: false },
+        _ if tag == "/unless" => Token::EndIf { negate: true },
+        _ => Token::Var(tag),
+    };
+
+    match token {
+        Token::Var(name) | Token::If { name, .. } if !is_name(name) => None,
+        token => Some(token),
+    }
+}
+
+fn is_name(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Whether the text after an unclosed `{{` was meant to be a tag
+fn starts_tag(text: &str) -> bool {
+    text.trim_start()
+        .starts_with(|c: char| c.is_ascii_alphabetic() || matches!(c, '_' | '#' | '/'))
+}
+
+fn ends_line(tokens: &[(Token, usize)]) -> bool {
+    match tokens.last() {
+        Some((Token::Text(text), _)) => text.ends_with('\n'),
+        Some((Token::Var(_), _)) => false,
+        _ => true,
+    }
+}
+
+fn parse_nodes<'a, I>(
+    tokens: &mut std::iter::Peekable<I>,
+    open_line: Option<usize>,
+) -> Result<Vec<Node<'a>>, TemplateError>
+where
+    I: Iterator<Item = (Token<'a>, usize)>,
+{
+    let mut nodes = Vec::new();
+
+    while let Some((token, line)) = tokens.peek() {
+        let line = *line;
+        match token {
+            Token::Else | Token::EndIf { .. } => {
+                if open_line.is_none() {
+                    let tag = match token {
+                        Token::EndIf { negate } => end_tag(*negate),
+                        _ => "else",
+                    };
+                    return Err(TemplateError::UnexpectedTag {
+                        tag: tag.to_string(),
+                        line,
+                    });
+                }
+                return Ok(nodes);
+            }
+            _ => {}
+        }
+
+        match tokens.next().expect("Peeked token") {
+            (Token::Text(text), _) => nodes.push(Node::Text(text)),
+            (Token::Var(name), line) => nodes.push(Node::Var(name, line)),
+            (Token::If { name, negate }, line) => {
+                let then = parse_nodes(tokens, Some(line))?;
+                let otherwise = match tokens.next() {
+                    Some((Token::Else, _)) => {
+                        let otherwise = parse_nodes(tokens, Some(line))?;
+                        match tokens.next() {
+                            Some((Token::EndIf { negate: closing }, end_line)) => {
+                                check_end(negate, closing, end_line)?;
+                                otherwise
+                            }
+                            Some((_, line)) => {
+                                return Err(TemplateError::UnexpectedTag {
+                                    tag: "else".to_string(),
+                                    line,
+                                })
+                            }
+                            None => return Err(TemplateError::UnclosedBlock { line }),
+                        }
+                    }
+                    Some((Token::EndIf { negate: closing }, end_line)) => {
+                        check_end(negate, closing, end_line)?;
+                        Vec::new()
+                    }
+                    _ => return Err(TemplateError::UnclosedBlock { line }),
+                };
+                nodes.push(Node::If {
+                    name,
+                    negate,
+                    then,
+                    otherwise,
+                });
+            }
+            _ => unreachable!("Block ends are handled above"),
+        }
+    }
+
+    match open_line {
+        Some(line) => Err(TemplateError::UnclosedBlock { line }),
+        None => Ok(nodes),
+    }
+}
+
+fn end_tag(negate: bool) -> &'static str {
+    match negate {
+        true => "/unless",
+        false => "/if",
+    }
+}
+
+/// An `{{#if}}` has to end with `{{/if}}` and an `{{#unless}}` with `{{/unless}}`
+fn check_end(negate: bool, closing: bool, line: usize) -> Result<(), TemplateError> {
+    match negate == closing {
+        true => Ok(()),
+        false => Err(TemplateError::UnexpectedTag {
+            tag: end_tag(closing).to_string(),
+            line,
+        }),
+    }
+}
+
+fn render_nodes(nodes: &[Node], context: &Context, out: &mut String) -> Result<(), TemplateError> {
+    for node in nodes {
+        match node {
+            Node::Text(text) => out.push_str(text),
+            Node::Var(name, line) => {
+                let value = context
+                    .get(name)
+                    .ok_or_else(|| TemplateError::UnknownVariable {
+                        name: name.to_string(),
+                        line: *line,
+                    })?;
+                out.push_str(value);
+            }
+            Node::If {
+                name,
+                negate,
+                then,
+                otherwise,
+            } => {
+                let branch = if context.is_truthy(name) != *negate {
+                    then
+                } else {
+                    otherwise
+                };
+                render_nodes(branch, context, out)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn context() -> Context {
+        let mut context = Context::new();
+        context
+            .set("day", "7")
+            .set("day_padded", "07")
+            .set("year", "2025")
+            .set("empty", "");
+        context
+    }
+
+    #[test]
+    fn test_variables() {
+        let rendered = render("name = \"day_{{day_padded}}\" # {{ year }}", &context());
+
+        assert_eq!(rendered, Ok("name = \"day_07\" # 2025".to_string()));
+    }
+
+    #[test]
+    fn test_text_without_tags_is_untouched() {
+        let text = "fn main() {\n    println!(\"{}\", 1);\n}\n";
+
+        assert_eq!(render(text, &context()), Ok(text.to_string()));
+    }
+
+    #[test]
+    fn test_braces_that_are_not_tags() {
+        let text = "format!(\"{{}}\");\nprintln!(\"{{:?}} {{\", x);\nlet y = \"{{ 1 + 2 }}\";\n";
+        assert_eq!(render(text, &context()), Ok(text.to_string()));
+
+        let template = "write!(f, \"{{{{}}}} day {{day}}\")";
+        assert_eq!(
+            render(template, &context()),
+            Ok("write!(f, \"{{{{}}}} day 7\")".to_string())
+        );
+    }
+
+    #[test]
+    fn test_if_else() {
+        let template = "{{#if year}}Year {{year}}{{else}}No year{{/if}}";
+        assert_eq!(render(template, &context()), Ok("Year 2025".to_string()));
+
+        let template = "{{#if empty}}Set{{else}}Unset{{/if}}";
+        assert_eq!(render(template, &context()), Ok("Unset".to_string()));
+
+        let template = "{{#unless missing}}Unset{{/unless}}";
+        assert_eq!(render(template, &context()), Ok("Unset".to_string()));
+    }
+
+    #[test]
+    fn test_block_lines_are_removed() {
+        let template = "a\n{{#if year}}\nb\n{{else}}\nc\n{{/if}}\nd\n";
+
+        assert_eq!(render(template, &context()), Ok("a\nb\nd\n".to_string()));
+    }
+
+    #[test]
+    fn test_nested_blocks() {
+        let template = "{{#if year}}{{#if empty}}x{{else}}y{{/if}}{{/if}}";
+
+        assert_eq!(render(template, &context()), Ok("y".to_string()));
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(
+            render("a\n{{nope}}", &context()),
+            Err(TemplateError::UnknownVariable {
+                name: "nope".to_string(),
+                line: 2
+            })
+        );
+        assert_eq!(
+            render("{{#if year}}\nx", &context()),
+            Err(TemplateError::UnclosedBlock { line: 1 })
+        );
+        assert_eq!(
+            render("x {{/if}}", &context()),
+            Err(TemplateError::UnexpectedTag {
+                tag: "/if".to_string(),
+                line: 1
+            })
+        );
+        assert_eq!(
+            render("{{#if year}}\nx\n{{/unless}}", &context()),
+            Err(TemplateError::UnexpectedTag {
+                tag: "/unless".to_string(),
+                line: 3
+            })
+        );
+        assert_eq!(
+            render("{{#unless year}}x{{else}}y{{/if}}", &context()),
+            Err(TemplateError::UnexpectedTag {
+                tag: "/if".to_string(),
+                line: 1
+            })
+        );
+        assert_eq!(
+            render("{{day", &context()),
+            Err(TemplateError::UnclosedTag { line: 1 })
+        );
+    }
+}
@@ -0,0 +1,380 @@
+use assert_cmd::cargo::cargo_bin_cmd;
+use assert_cmd::Command;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+fn data_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("data")
+}
+
+fn builder(out_dir: &Path, source_dir: &Path) -> Command {
+    let mut cmd = cargo_bin_cmd!("advent_of_code_template_builder");
+    cmd.arg("--out-dir")
+        .arg(out_dir)
+        .arg("--source-dir")
+        .arg(source_dir);
+    cmd
+}
+
+#[test]
+fn test_creates_year_workspace() -> Result<(), Box<dyn std::error::Error>> {
+    let out = tempfile::tempdir()?;
+
+    let assert = builder(out.path(), &data_dir())
+        .args(["--year", "2030", "--day", "1"])
+        .assert()
+        .success();
+    assert!(String::from_utf8_lossy(&assert.get_output().stdout).contains("Year 2030 is ready"));
+
+    let assert = builder(out.path(), &data_dir())
+        .args(["--year", "2030", "--day", "2"])
+        .assert()
+        .success();
+    assert!(!String::from_utf8_lossy(&assert.get_output().stdout).contains("is ready"));
+
+    let year = out.path().join("2030");
+    assert!(fs::read_to_string(year.join("Cargo.toml"))?.contains("members = [\"day_*\"]"));
+    assert!(
+        fs::read_to_string(year.join("readme.md"))?.starts_with("# Advent of Code in Rust 2030")
+    );
+    assert!(fs::read_to_string(year.join("day_01/Cargo.toml"))?.contains("name = \"day_01\""));
+    assert!(year.join("day_01/examples/example.txt").exists());
+    assert!(year.join("day_01/examples/example.expected").exists());
+
+    Ok(())
+}
+
+#[test]
+fn test_existing_day_is_an_error() -> Result<(), Box<dyn std::error::Error>> {
+    let out = tempfile::tempdir()?;
+    fs::create_dir_all(out.path().join("day_02"))?;
+
+    builder(out.path(), &data_dir())
+        .args(["--day", "2"])
+        .assert()
+        .code(73);
+
+    Ok(())
+}
+
+#[test]
+fn test_missing_template_is_an_error() -> Result<(), Box<dyn std::error::Error>> {
+    let out = tempfile::tempdir()?;
+    let source = tempfile::tempdir()?;
+
+    builder(out.path(), source.path())
+        .args(["--day", "3"])
+        .assert()
+        .code(66);
+
+    Ok(())
+}
+
+#[test]
+fn test_broken_template_rolls_back() -> Result<(), Box<dyn std::error::Error>> {
+    let out = tempfile::tempdir()?;
+    let source = tempfile::tempdir()?;
+
+    copy_dir(&data_dir(), source.path())?;
+    fs::write(source.path().join("lines/zzz.txt"), "{{not_a_variable}}")?;
+
+    builder(out.path(), source.path())
+        .args(["--year", "2030", "--day", "3"])
+        .assert()
+        .code(65);
+
+    assert!(!out.path().join("2030").exists());
+
+    Ok(())
+}
+
+#[test]
+fn test_renders_variables() -> Result<(), Box<dyn std::error::Error>> {
+    let out = tempfile::tempdir()?;
+
+    builder(out.path(), &data_dir())
+        .args([
+            "--year",
+            "2030",
+            "--day",
+            "5",
+            "--title",
+            "Day 5: Print Queue",
+        ])
+        .assert()
+        .success();
+
+    let day = out.path().join("2030/day_05");
+    let readme = fs::read_to_string(day.join("readme.md"))?;
+    assert!(readme.starts_with("# Day 5: Print Queue\n"));
+    assert!(readme.contains("https://adventofcode.com/2030/day/5"));
+    assert!(fs::read_to_string(day.join("tests/test_example.rs"))?
+        .contains("cargo_bin_cmd!(\"day_05\")"));
+
+    Ok(())
+}
+
+#[test]
+fn test_force_backs_up_solution_and_input() -> Result<(), Box<dyn std::error::Error>> {
+    let out = tempfile::tempdir()?;
+    let day = out.path().join("day_04");
+    fs::create_dir_all(day.join("src"))?;
+    fs::write(day.join("src/main.rs"), "fn main() {}")?;
+    fs::write(day.join("src/lib.rs"), "pub struct Day04;")?;
+    fs::write(day.join("input.txt"), "my input")?;
+
+    builder(out.path(), &data_dir())
+        .args(["--day", "4", "--force"])
+        .assert()
+        .success();
+
+    assert_eq!(
+        fs::read_to_string(day.join("src/main.rs.bak"))?,
+        "fn main() {}"
+    );
+    assert_eq!(
+        fs::read_to_string(day.join("src/lib.rs.bak"))?,
+        "pub struct Day04;"
+    );
+    assert_eq!(fs::read_to_string(day.join("input.txt.bak"))?, "my input");
+    assert!(day.join("tests/test_example.rs").exists());
+
+    Ok(())
+}
+
+#[test]
+fn test_force_rolls_back_overwritten_files() -> Result<(), Box<dyn std::error::Error>> {
+    let out = tempfile::tempdir()?;
+    let source = tempfile::tempdir()?;
+
+    copy_dir(&data_dir(), source.path())?;
+    fs::write(source.path().join("lines/zzz.txt"), "{{not_a_variable}}")?;
+
+    let day = out.path().join("day_04");
+    fs::create_dir_all(day.join("src"))?;
+    fs::create_dir_all(day.join("tests"))?;
+    fs::write(day.join("Cargo.toml"), "my manifest")?;
+    fs::write(day.join("readme.md"), "my notes")?;
+    fs::write(day.join("src/main.rs"), "fn main() {}")?;
+    fs::write(day.join("tests/test_example.rs"), "my test")?;
+
+    builder(out.path(), source.path())
+        .args(["--day", "4", "--force"])
+        .assert()
+        .code(65);
+
+    assert_eq!(fs::read_to_string(day.join("Cargo.toml"))?, "my manifest");
+    assert_eq!(fs::read_to_string(day.join("readme.md"))?, "my notes");
+    assert_eq!(fs::read_to_string(day.join("src/main.rs"))?, "fn main() {}");
+    assert_eq!(
+        fs::read_to_string(day.join("tests/test_example.rs"))?,
+        "my test"
+    );
+    assert!(!day.join("src/main.rs.bak").exists());
+    assert!(!day.join("src/lib.rs").exists());
+
+    Ok(())
+}
+
+#[test]
+fn test_updates_indexes() -> Result<(), Box<dyn std::error::Error>> {
+    let out = tempfile::tempdir()?;
+    fs::write(
+        out.path().join("readme.md"),
+        "## Years\n[2031](2031)\n[2029](2029)\n",
+    )?;
+
+    for day in ["2", "1"] {
+        builder(out.path(), &data_dir())
+            .args(["--year", "2030", "--day", day])
+            .assert()
+            .success();
+    }
+
+    assert_eq!(
+        fs::read_to_string(out.path().join("readme.md"))?,
+        "## Years\n[2031](2031)\n[2030](2030)\n[2029](2029)\n"
+    );
+
+    let year_readme = fs::read_to_string(out.path().join("2030/readme.md"))?;
+    assert!(year_readme
+        .ends_with("## Days\n\n- [Day 1](day_01/readme.md)\n- [Day 2](day_02/readme.md)\n"));
+
+    Ok(())
+}
+
+#[test]
+fn test_kinds() -> Result<(), Box<dyn std::error::Error>> {
+    let out = tempfile::tempdir()?;
+
+    for (day, kind) in ["lines", "grid", "nom", "vm"].iter().enumerate() {
+        builder(out.path(), &data_dir())
+            .args(["--day", &(day + 1).to_string(), "--kind", kind])
+            .assert()
+            .success();
+    }
+
+    let nom_cargo = fs::read_to_string(out.path().join("day_03/Cargo.toml"))?;
+    assert!(nom_cargo.contains("nom = "));
+    let grid_lib = fs::read_to_string(out.path().join("day_02/src/lib.rs"))?;
+    assert!(grid_lib.contains("use aoc_common::{Answer, Grid, Solution};"));
+    let grid_main = fs::read_to_string(out.path().join("day_02/src/main.rs"))?;
+    assert!(grid_main.contains("run::<Day02>"));
+
+    // The shared files from data/common go into every kind
+    for day in ["day_01", "day_02", "day_03", "day_04"] {
+        let test = fs::read_to_string(out.path().join(day).join("tests/test_example.rs"))?;
+        assert!(test.contains(&format!("cargo_bin_cmd!(\"{day}\")")));
+        assert!(out.path().join(day).join("readme.md").exists());
+        assert!(out.path().join(day).join("input.txt").exists());
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_migrate() -> Result<(), Box<dyn std::error::Error>> {
+    let out = tempfile::tempdir()?;
+    let day = out.path().join("day_07");
+    fs::create_dir_all(day.join("src"))?;
+    fs::create_dir_all(day.join("tests"))?;
+    fs::write(
+        day.join("Cargo.toml"),
+        "[package]\nname = \"day_07\"\nversion = \"0.1.0\"\nedition = \"2021\"\n\n[dependencies]\nitertools=\"0.9.0\"\n\n[dev-dependencies]\nassert_cmd = \"2.0.2\"",
+    )?;
+    fs::write(day.join("src/main.rs"), "fn main() {}")?;
+    fs::write(day.join("example.txt"), "1 2 3")?;
+    fs::write(
+        day.join("tests/test_example.rs"),
+        "#[test]\nfn test_example() {\n    file_path.push(\"example.txt\");\n    assert.stdout(\"Total is 6\\n\");\n}\n",
+    )?;
+
+    let mut cmd = cargo_bin_cmd!("advent_of_code_template_builder");
+    cmd.arg("migrate")
+        .arg(&day)
+        .arg("--source-dir")
+        .arg(data_dir())
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("+assert_cmd = \"2.1.1\""));
+
+    let cargo = fs::read_to_string(day.join("Cargo.toml"))?;
+    assert!(cargo.contains("itertools=\"0.9.0\""));
+    assert!(cargo.contains("assert_cmd = \"2.1.1\""));
+    assert!(!cargo.contains("[package.metadata.aoc]"));
+    assert_eq!(fs::read_to_string(day.join("src/main.rs"))?, "fn main() {}");
+    assert_eq!(
+        fs::read_to_string(day.join("examples/example.txt"))?,
+        "1 2 3"
+    );
+    assert_eq!(
+        fs::read_to_string(day.join("examples/example.expected"))?,
+        "Total is 6\n"
+    );
+    assert!(fs::read_to_string(day.join("tests/test_example.rs"))?.contains("fn test_examples"));
+
+    Ok(())
+}
+
+#[test]
+fn test_dry_run_writes_nothing() -> Result<(), Box<dyn std::error::Error>> {
+    let out = tempfile::tempdir()?;
+    fs::write(out.path().join("readme.md"), "## Years\n[2029](2029)\n")?;
+
+    builder(out.path(), &data_dir())
+        .args(["--year", "2030", "--day", "4", "--dry-run"])
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("    day_04/\n      Cargo.toml\n"))
+        .stdout(predicates::str::contains("name = \"day_04\""))
+        .stdout(predicates::str::contains("- [Day 4](day_04/readme.md)"))
+        .stdout(predicates::str::contains("+[2030](2030)"));
+
+    assert!(!out.path().join("2030").exists());
+    assert_eq!(
+        fs::read_to_string(out.path().join("readme.md"))?,
+        "## Years\n[2029](2029)\n"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_list() -> Result<(), Box<dyn std::error::Error>> {
+    let out = tempfile::tempdir()?;
+
+    for day in ["1", "2", "3", "5"] {
+        builder(out.path(), &data_dir())
+            .args(["--year", "2030", "--day", day])
+            .assert()
+            .success();
+    }
+    let year = out.path().join("2030");
+    for day in ["day_01", "day_02", "day_05"] {
+        let lib = year.join(day).join("src/lib.rs");
+        let solved = fs::read_to_string(&lib)?.replacen("Answer::Unsolved", "6.into()", 1);
+        fs::write(lib, solved)?;
+    }
+    for day in ["day_01", "day_03", "day_05"] {
+        fs::write(year.join(day).join("input.txt"), "1 2 3")?;
+    }
+    fs::write(year.join("day_05/examples/example.txt"), "\n")?;
+
+    let mut cmd = cargo_bin_cmd!("advent_of_code_template_builder");
+    cmd.arg("list")
+        .arg("--root")
+        .arg(out.path())
+        .assert()
+        .success()
+        .stdout(
+            "2030: 4 days (1-3, 5)\n  day_02: empty input.txt\n  day_03: stub\n  day_05: empty examples/example.txt\n",
+        );
+
+    Ok(())
+}
+
+#[test]
+fn test_from_html() -> Result<(), Box<dyn std::error::Error>> {
+    let out = tempfile::tempdir()?;
+    let page = out.path().join("day12.html");
+    fs::write(
+        &page,
+        "<html><body><main><article class=\"day-desc\"><h2>--- Day 12: Garden Groups ---</h2><p>Fence the <em>regions</em>.</p><pre><code>AAAA\nBBCD\n</code></pre></article><p>Your puzzle answer was <code>1930</code>.</p></main></body></html>",
+    )?;
+
+    builder(out.path(), &data_dir())
+        .args(["--year", "2030", "--day", "12", "--from-html"])
+        .arg(&page)
+        .assert()
+        .success();
+
+    let day = out.path().join("2030/day_12");
+    let readme = fs::read_to_string(day.join("readme.md"))?;
+    assert!(readme.starts_with("# Day 12: Garden Groups\n"));
+    assert!(readme
+        .contains("## Part 1\n\nFence the **regions**.\n\n```\nAAAA\nBBCD\n```\n\n## Part 2\n"));
+    assert_eq!(
+        fs::read_to_string(day.join("examples/example.txt"))?,
+        "AAAA\nBBCD\n"
+    );
+    assert!(fs::read_to_string(out.path().join("2030/answers.toml"))?
+        .ends_with("\n\n[day_12]\npart1 = 1930\n"));
+    assert!(fs::read_to_string(out.path().join("2030/readme.md"))?
+        .contains("- [Day 12: Garden Groups](day_12/readme.md)"));
+
+    Ok(())
+}
+
+fn copy_dir(from: &Path, to: &Path) -> std::io::Result<()> {
+    fs::create_dir_all(to)?;
+    for entry in fs::read_dir(from)? {
+        let entry = entry?;
+        let target = to.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            copy_dir(&entry.path(), &target)?;
+        } else {
+            fs::copy(entry.path(), target)?;
+        }
+    }
+    Ok(())
+}
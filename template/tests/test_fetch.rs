@@ -0,0 +1,143 @@
+use assert_cmd::cargo::cargo_bin_cmd;
+use assert_cmd::Command;
+use std::fs;
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+
+const SESSION: &str = "53616c7465645f5f";
+
+/// Serves `body` as every day's input to requests with the right session
+/// cookie, counting how many requests it has had
+fn stub_server(body: &'static str) -> (String, Arc<AtomicUsize>) {
+    let server = tiny_http::Server::http("127.0.0.1:0").expect("Stub server starts");
+    let url = format!(
+        "http://{}",
+        server.server_addr().to_ip().expect("Stub server is on IP")
+    );
+    let requests = Arc::new(AtomicUsize::new(0));
+    let counter = requests.clone();
+
+    thread::spawn(move || {
+        for request in server.incoming_requests() {
+            counter.fetch_add(1, Ordering::SeqCst);
+
+            let cookie = format!("session={SESSION}");
+            let logged_in = request
+                .headers()
+                .iter()
+                .any(|h| h.field.equiv("Cookie") && h.value.as_str() == cookie);
+            let response = match logged_in && request.url().ends_with("/input") {
+                true => tiny_http::Response::from_string(body),
+                false => tiny_http::Response::from_string("Puzzle inputs differ by user.")
+                    .with_status_code(400),
+            };
+            let _ = request.respond(response);
+        }
+    });
+
+    (url, requests)
+}
+
+fn fetch(root: &Path, url: &str, day: &str) -> Command {
+    let mut cmd = cargo_bin_cmd!("advent_of_code_template_builder");
+    cmd.arg("fetch")
+        .arg("--root")
+        .arg(root)
+        .arg("--cache-dir")
+        .arg(root.join("cache"))
+        .args(["--base-url", url, "--year", "2030", "--day", day])
+        .env("AOC_SESSION", SESSION);
+    cmd
+}
+
+fn day_folder(root: &Path, day: &str, input: &str) -> std::io::Result<()> {
+    let day = root.join("2030").join(day);
+    fs::create_dir_all(&day)?;
+    fs::write(day.join("input.txt"), input)
+}
+
+#[test]
+fn test_fetch_is_cached() -> Result<(), Box<dyn std::error::Error>> {
+    let root = tempfile::tempdir()?;
+    let (url, requests) = stub_server("1 2 3\n");
+    day_folder(root.path(), "day_01", "")?;
+
+    fetch(root.path(), &url, "1").assert().success();
+
+    let input = root.path().join("2030/day_01/input.txt");
+    assert_eq!(fs::read_to_string(&input)?, "1 2 3\n");
+    assert_eq!(
+        fs::read_to_string(root.path().join("cache/2030/day_01.txt"))?,
+        "1 2 3\n"
+    );
+
+    fetch(root.path(), &url, "1")
+        .arg("--force")
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("Using the cached input"));
+
+    assert_eq!(requests.load(Ordering::SeqCst), 1);
+
+    Ok(())
+}
+
+#[test]
+fn test_fetch_keeps_existing_input() -> Result<(), Box<dyn std::error::Error>> {
+    let root = tempfile::tempdir()?;
+    let (url, requests) = stub_server("1 2 3\n");
+    day_folder(root.path(), "day_01", "my input")?;
+
+    fetch(root.path(), &url, "1").assert().code(73);
+
+    assert_eq!(
+        fs::read_to_string(root.path().join("2030/day_01/input.txt"))?,
+        "my input"
+    );
+    assert_eq!(requests.load(Ordering::SeqCst), 0);
+
+    Ok(())
+}
+
+#[test]
+fn test_fetch_needs_a_session() -> Result<(), Box<dyn std::error::Error>> {
+    let root = tempfile::tempdir()?;
+    let (url, _) = stub_server("1 2 3\n");
+    day_folder(root.path(), "day_01", "")?;
+
+    fetch(root.path(), &url, "1")
+        .env_remove("AOC_SESSION")
+        .assert()
+        .code(78);
+
+    fetch(root.path(), &url, "1")
+        .env("AOC_SESSION", "wrong")
+        .assert()
+        .code(69)
+        .stderr(predicates::str::contains("session token was not accepted"));
+
+    assert!(!root.path().join("cache/2030/day_01.txt").exists());
+
+    Ok(())
+}
+
+#[test]
+fn test_fetch_is_throttled() -> Result<(), Box<dyn std::error::Error>> {
+    let root = tempfile::tempdir()?;
+    let (url, requests) = stub_server("1 2 3\n");
+    day_folder(root.path(), "day_01", "")?;
+    day_folder(root.path(), "day_02", "")?;
+
+    fetch(root.path(), &url, "1").assert().success();
+    fetch(root.path(), &url, "2")
+        .args(["--throttle", "1"])
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("Waiting"));
+
+    assert_eq!(requests.load(Ordering::SeqCst), 2);
+
+    Ok(())
+}